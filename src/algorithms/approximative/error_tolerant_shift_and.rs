@@ -1,14 +1,20 @@
+use crate::algorithms::occurrences::Occurrences;
 use crate::algorithms::single_pattern::shift_and::shift_and_single_masks;
 
-/// Returns occurrences of a pattern in a text given a maximum error.
+/// Reports occurrences of a pattern in a text given a maximum error, each
+/// together with its error.
 ///
 /// This is a modified version of the Shift-And Algorithm, simulating multiple
 /// NFAs to account for the pattern being able to have a given edit distance
 /// from the patterns in the text (the error).
-pub fn error_tolerant_shift_and(pattern: &[u8], text: &[u8], k: usize) -> Vec<(usize, usize)> {
+pub fn error_tolerant_shift_and(
+    pattern: &[u8],
+    text: &[u8],
+    k: usize,
+    occurrences: &mut Occurrences<(usize, usize)>,
+) {
     let m = pattern.len();
 
-    let mut occurrences: Vec<(usize, usize)> = Vec::new();
     let mut active: Vec<usize> = vec![0; k + 1];
     let (mask, ones, accept) = shift_and_single_masks(pattern);
 
@@ -31,7 +37,10 @@ pub fn error_tolerant_shift_and(pattern: &[u8], text: &[u8], k: usize) -> Vec<(u
         // position will be added to the occurrences
         let mut occurrence_added = false;
         if active[0] & accept != 0 {
-            occurrences.push((pos - m + 1, 0));
+            if !occurrences.report((pos + 1 - m, 0)) {
+                return;
+            }
+
             occurrence_added = true;
         }
 
@@ -39,26 +48,31 @@ pub fn error_tolerant_shift_and(pattern: &[u8], text: &[u8], k: usize) -> Vec<(u
             active[i] |= active[i - 1] << 1;
 
             if active[i] & accept != 0 && !occurrence_added {
-                occurrences.push((pos - m + 1, i));
+                if !occurrences.report((pos + 1 - m, i)) {
+                    return;
+                }
+
                 occurrence_added = true;
             }
         }
     }
-
-    occurrences
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::algorithms::occurrences::QueryMode;
+
     #[test]
     fn test_error_tolerant_shift_and() {
         let text = b"dddddabcddd";
         let pattern = b"abc";
         let k = 1;
 
-        let matches = error_tolerant_shift_and(pattern, text, k);
+        let mut occurrences = Occurrences::new(QueryMode::All);
+        error_tolerant_shift_and(pattern, text, k, &mut occurrences);
+        let matches = occurrences.into_positions();
 
         println!("{:?}", matches);
 
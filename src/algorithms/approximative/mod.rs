@@ -1,29 +1,38 @@
 pub mod error_tolerant_shift_and;
 pub mod ukkonen;
 
-use std::time::SystemTime;
-
+use crate::algorithms::occurrences::Occurrences;
 use crate::cli::CLIParams;
 use crate::match_algorithm::ApproximativeAlgorithm;
-use crate::measure::measurement::SingleMeasurement;
+use crate::measure::measurement::{measure_phase, SingleMeasurement};
 use crate::measure::Measure;
 
 impl Measure for ApproximativeAlgorithm {
     /// A function to measure the runtime of an approximative algorithm.
     ///
-    /// It takes the maximum allowed error from the given CLI parameters.
+    /// It takes the maximum allowed error and the query mode from the given
+    /// CLI parameters.
     #[cfg(not(tarpaulin_include))]
     fn measure(&self, pattern: &[u8], text: &[u8], cli_params: &CLIParams) -> SingleMeasurement {
-        let before = SystemTime::now();
+        // Unwrapping the `maximum_error` and `query_mode` CLI parameters is
+        // valid here because they can't be None as checked in `cli::valid()`
+        let maximum_error = cli_params.maximum_error.unwrap();
+        let query_mode = cli_params.query_mode.unwrap();
 
-        // Unwrapping the `maximum_error` CLI parameter is valid here
-        // because it can't be None as checked in `cli::valid()`
-        let matches = self(pattern, text, cli_params.maximum_error.unwrap()).len();
+        let (matches, algorithm) = measure_phase(cli_params, || {
+            let mut occurrences = Occurrences::new(query_mode);
+            self(pattern, text, maximum_error, &mut occurrences);
 
-        let duration = before.elapsed();
+            occurrences.count()
+        });
 
         // Because these algorithms do not have a preparation phase the runtime
-        // of which could be measured, the first value is simply None
-        (None, duration.expect("Could not measure time."), matches)
+        // of which could be measured, the preparation is simply None
+        SingleMeasurement {
+            preparation: None,
+            algorithm,
+            matches,
+            counts: None,
+        }
     }
 }
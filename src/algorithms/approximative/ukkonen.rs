@@ -1,14 +1,21 @@
 use std::cmp::{min, Ordering};
 use std::mem::swap;
 
-/// Returns occurrences of a pattern in a text given a maximum error.
+use crate::algorithms::occurrences::Occurrences;
+
+/// Reports occurrences of a pattern in a text given a maximum error, each
+/// together with its error.
 ///
 /// The runtime is `O(kn)` given a maximum error `k` and a text with length `n`.
-pub fn ukkonen(pattern: &[u8], text: &[u8], k: usize) -> Vec<(usize, usize)> {
+pub fn ukkonen(
+    pattern: &[u8],
+    text: &[u8],
+    k: usize,
+    occurrences: &mut Occurrences<(usize, usize)>,
+) {
     let m = pattern.len();
     let n = text.len();
 
-    let mut occurrences: Vec<(usize, usize)> = Vec::new();
     #[allow(unused_assignments)]
     let mut d_o: Vec<usize> = Vec::with_capacity(m + 1);
     #[allow(unused_assignments)]
@@ -38,12 +45,10 @@ pub fn ukkonen(pattern: &[u8], text: &[u8], k: usize) -> Vec<(usize, usize)> {
             last_k -= 1;
         }
 
-        if last_k == m {
-            occurrences.push((j - m, d_j[m]));
+        if last_k == m && !occurrences.report((j - m, d_j[m])) {
+            return;
         }
     }
-
-    occurrences
 }
 
 fn ukkonen_cost_one(a: &u8, b: &u8) -> usize {
@@ -57,13 +62,17 @@ fn ukkonen_cost_one(a: &u8, b: &u8) -> usize {
 mod tests {
     use super::*;
 
+    use crate::algorithms::occurrences::QueryMode;
+
     #[test]
     fn test_ukkonen() {
         let text = b"dddddabcddd";
         let pattern = b"abc";
         let k = 1;
 
-        let matches = ukkonen(pattern, text, k);
+        let mut occurrences = Occurrences::new(QueryMode::All);
+        ukkonen(pattern, text, k, &mut occurrences);
+        let matches = occurrences.into_positions();
 
         println!("{:?}", matches);
 
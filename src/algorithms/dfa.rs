@@ -29,7 +29,7 @@ pub fn dfa_with_delta(
         q = delta(q, *c, pattern);
 
         if q == (m - 1) as isize {
-            return Some(i - m + 1);
+            return Some(i + 1 - m);
         }
     }
 
@@ -66,7 +66,7 @@ pub fn dfa_with_lps_delta(
         q = delta(q, *c, pattern, lps);
 
         if q == (m - 1) as isize {
-            return Some(i - m + 1);
+            return Some(i + 1 - m);
         }
     }
 
@@ -0,0 +1,71 @@
+use crate::algorithms::full_text_indices::suffix_array::{bwt, less, occ};
+use crate::match_algorithm::SuffixArrayGenAlgorithm;
+
+/// A full-text index of a text consisting of the text's suffix array and,
+/// if required by the algorithm using the index, the Occ and less arrays of
+/// the text's Burrows-Wheeler-Transformation (BWT).
+pub struct FullTextIndex {
+    /// The indexed text including a sentinel at its end.
+    pub text: Vec<u8>,
+    pub pos: Vec<usize>,
+    pub bwt_index: Option<BWTIndex>,
+}
+
+/// The Occ and less arrays of a text's Burrows-Wheeler-Transformation.
+pub struct BWTIndex {
+    pub occ: Vec<usize>,
+    pub less: Vec<usize>,
+}
+
+impl FullTextIndex {
+    /// Adds a sentinel to the given text and builds its suffix array using
+    /// the given suffix array generation function.
+    pub fn new(text: &[u8], suffix_array_gen_algorithm: SuffixArrayGenAlgorithm) -> Self {
+        let mut text = text.to_vec();
+        text.push(0);
+
+        let pos = suffix_array_gen_algorithm(&text);
+
+        Self {
+            text,
+            pos,
+            bwt_index: None,
+        }
+    }
+}
+
+impl BWTIndex {
+    /// Calculates the BWT of a text using its suffix array and builds the
+    /// Occ and less arrays of the BWT.
+    pub fn new(text: &[u8], pos: &[usize]) -> Self {
+        let bwt_vec = bwt(text, pos);
+
+        Self {
+            occ: occ(&bwt_vec),
+            less: less(&bwt_vec),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::full_text_indices::suffix_array::slow;
+
+    #[test]
+    fn test_full_text_index() {
+        let mut index = FullTextIndex::new(b"gccttaacattattacgccta", slow);
+        index.bwt_index = Some(BWTIndex::new(&index.text, &index.pos));
+
+        let pos_correct = vec![
+            21, 20, 5, 6, 14, 11, 8, 7, 17, 1, 15, 18, 2, 16, 0, 19, 4, 13, 10, 3, 12, 9,
+        ];
+        let bwt_vec = b"attattcaggaccc\0ctttcaa";
+
+        assert_eq!(index.text, b"gccttaacattattacgccta\0");
+        assert_eq!(index.pos, pos_correct);
+        assert_eq!(index.bwt_index.as_ref().unwrap().occ, occ(bwt_vec));
+        assert_eq!(index.bwt_index.as_ref().unwrap().less, less(bwt_vec));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod full_text_index;
 pub mod sais;
 pub mod suffix_array;
 pub mod suffix_array_algorithms;
@@ -1,100 +1,112 @@
 use std::cmp::{max, min, Ordering};
-use std::time::SystemTime;
+use std::ops::Range;
 
-use crate::algorithms::full_text_indices::suffix_array::{bwt, less, occ};
+use crate::algorithms::full_text_indices::full_text_index::FullTextIndex;
+use crate::algorithms::occurrences::Occurrences;
 use crate::cli::CLIParams;
-use crate::match_algorithm::{
-    match_suffix_array_gen_algorithm, BWTAlgorithm, SuffixArrayAlgorithm,
-};
-use crate::measure::measurement::SingleMeasurement;
-use crate::measure::Measure;
+use crate::match_algorithm::{BWTAlgorithm, SuffixArrayAlgorithm};
+use crate::measure::measurement::{measure_phase, SingleMeasurement};
+use crate::measure::IndexMeasure;
 
-impl Measure for SuffixArrayAlgorithm {
+impl IndexMeasure for SuffixArrayAlgorithm {
     /// A function to measure the runtime of an algorithm that requires a
     /// suffix array to work.
     ///
-    /// It separately measures both the preparation time, i. e. the time it takes
-    /// to generate the suffix array using the given suffix array generation
-    /// function and the execution time, i. e. the time it takes to execute
-    /// the actual algorithm itself.
+    /// The suffix array has already been generated when building the given
+    /// index, so only the execution time, i. e. the time it takes to find the
+    /// interval of the suffix array containing the occurrences and to answer
+    /// the query given by the CLI parameters from it, is measured.
     #[cfg(not(tarpaulin_include))]
-    fn measure(&self, pattern: &[u8], text: &[u8], cli_params: &CLIParams) -> SingleMeasurement {
-        // Add sentinel to text
-        let mut text = text.iter().copied().collect::<Vec<u8>>();
-        text.push(0);
-        let text = text.as_slice();
-
-        // Measure time it takes to generate the suffix array
-        let before = SystemTime::now();
-
-        let pos =
-            match_suffix_array_gen_algorithm(&cli_params.suffix_array_algorithm).unwrap()(text);
-
-        let preparation_duration = before.elapsed();
-
-        // Measure time it takes to run the actual algorithm
-        let before = SystemTime::now();
-
-        let matches = self(&pos, pattern, text).len();
-
-        let algorithm_duration = before.elapsed();
-
-        (
-            Some(preparation_duration.expect("Could not measure preparation time.")),
-            algorithm_duration.expect("Could not measure time."),
+    fn measure(
+        &self,
+        pattern: &[u8],
+        index: &FullTextIndex,
+        cli_params: &CLIParams,
+    ) -> SingleMeasurement {
+        // Unwrapping is safe here because of the checks done in cli_params.valid()
+        let query_mode = cli_params.query_mode.unwrap();
+
+        let (matches, algorithm) = measure_phase(cli_params, || {
+            let interval = self(&index.pos, pattern, &index.text);
+
+            let mut occurrences = Occurrences::new(query_mode);
+            occurrences.report_suffixes(&index.pos[interval]);
+
+            occurrences.count()
+        });
+
+        // The preparation, i. e. building the index, is measured only once
+        // when the index is built, so the preparation is simply None
+        SingleMeasurement {
+            preparation: None,
+            algorithm,
             matches,
-        )
+            counts: None,
+        }
     }
 }
 
-impl Measure for BWTAlgorithm {
-    /// A function to measure the runtime of an algorithm that requires a
-    /// suffix array to work.
+impl IndexMeasure for BWTAlgorithm {
+    /// A function to measure the runtime of an algorithm that requires the
+    /// BWT of a text to work.
     ///
-    /// It separately measures both the preparation time, i. e. the time it takes
-    /// to generate the suffix array using the given suffix array generation
-    /// function and the execution time, i. e. the time it takes to execute
-    /// the actual algorithm itself.
+    /// The suffix array as well as the Occ and less vectors have already been
+    /// built when building the given index, so only the execution time,
+    /// i. e. the time it takes to execute the backward search and to answer
+    /// the query given by the CLI parameters from the resulting interval of
+    /// the suffix array, is measured.
     #[cfg(not(tarpaulin_include))]
-    fn measure(&self, pattern: &[u8], text: &[u8], cli_params: &CLIParams) -> SingleMeasurement {
-        // Add sentinel to text
-        let mut text = text.iter().copied().collect::<Vec<u8>>();
-        text.push(0);
-        let text = text.as_slice();
-
-        // Measure time it takes to generate the suffix array
-        let before = SystemTime::now();
-
-        let pos =
-            match_suffix_array_gen_algorithm(&cli_params.suffix_array_algorithm).unwrap()(text);
-        let bwt_vec = bwt(text, &pos);
-        let occ_vec = occ(&bwt_vec);
-        let less_vec = less(&bwt_vec);
-
-        let preparation_duration = before.elapsed();
-
-        // Measure time it takes to run the actual algorithm
-        let before = SystemTime::now();
-
-        let matches = self(&pos, &occ_vec, &less_vec, pattern).len();
-
-        let algorithm_duration = before.elapsed();
-
-        (
-            Some(preparation_duration.expect("Could not measure preparation time.")),
-            algorithm_duration.expect("Could not measure time."),
+    fn measure(
+        &self,
+        pattern: &[u8],
+        index: &FullTextIndex,
+        cli_params: &CLIParams,
+    ) -> SingleMeasurement {
+        let bwt_index = index
+            .bwt_index
+            .as_ref()
+            .expect("The BWT index has not been built.");
+
+        // Unwrapping is safe here because of the checks done in cli_params.valid()
+        let query_mode = cli_params.query_mode.unwrap();
+
+        let (matches, algorithm) = measure_phase(cli_params, || {
+            let interval = self(&bwt_index.occ, &bwt_index.less, pattern);
+
+            let mut occurrences = Occurrences::new(query_mode);
+            occurrences.report_suffixes(&index.pos[interval]);
+
+            occurrences.count()
+        });
+
+        // The preparation, i. e. building the index, is measured only once
+        // when the index is built, so the preparation is simply None
+        SingleMeasurement {
+            preparation: None,
+            algorithm,
             matches,
-        )
+            counts: None,
+        }
     }
 }
 
 /// Returns occurrences of a pattern in a text using a precalculated suffix array.
 ///
+/// Using the interval found by `match_pattern_interval`, it extracts the
+/// beginning positions of the occurrences of the pattern in the text from the
+/// suffix array.
+#[allow(unused)]
+pub fn match_pattern(pos: &[usize], pattern: &[u8], text: &[u8]) -> Vec<usize> {
+    pos[match_pattern_interval(pos, pattern, text)].to_vec()
+}
+
+/// Returns the interval of a precalculated suffix array containing the
+/// occurrences of a pattern in a text.
+///
 /// The algorithm uses a modified binary search to find an interval in the
 /// given suffix array including those suffixes which have a prefix equal
-/// to the sought pattern. Using that interval, it then extracts the beginning
-/// positions of the occurrences of the pattern in the text from the suffix array.
-pub fn match_pattern(pos: &[usize], pattern: &[u8], text: &[u8]) -> Vec<usize> {
+/// to the sought pattern.
+pub fn match_pattern_interval(pos: &[usize], pattern: &[u8], text: &[u8]) -> Range<usize> {
     // Define the binary search function as a local function
     // because it is only needed here
     fn binary_search(
@@ -155,22 +167,33 @@ pub fn match_pattern(pos: &[usize], pattern: &[u8], text: &[u8]) -> Vec<usize> {
     // Call the binary search function over the entire suffix array
     let (l, r) = binary_search(&pos, pattern, text, 0, pos.len() - 1);
 
-    // Return empty vector if interval is empty (right bound is lower than left bound)
+    // Return an empty interval if the right bound is lower than the left bound
     if l <= r {
-        pos[l..=r].to_vec()
+        l..r + 1
     } else {
-        Vec::new()
+        0..0
     }
 }
 
 /// Returns occurrences of a pattern in a text using a precalculated
 /// Burrows-Wheeler-Transformation (BWT) of the text and the Occ and less vectors.
+#[allow(unused)]
 pub fn match_pattern_bwt(
     pos: &[usize],
     occ: &[usize],
     less: &[usize],
     pattern: &[u8],
 ) -> Vec<usize> {
+    pos[match_pattern_bwt_interval(occ, less, pattern)].to_vec()
+}
+
+/// Returns the interval of the suffix array containing the occurrences of a
+/// pattern in a text using the backward search on the Occ and less vectors of
+/// the text's Burrows-Wheeler-Transformation (BWT).
+///
+/// The suffix array itself isn't needed, so the occurrences can be counted
+/// without locating them.
+pub fn match_pattern_bwt_interval(occ: &[usize], less: &[usize], pattern: &[u8]) -> Range<usize> {
     let m = pattern.len();
     let n = occ.len() / 256;
 
@@ -185,7 +208,7 @@ pub fn match_pattern_bwt(
         right = less[c as usize] + occ[right * 256 + c as usize] - 1;
     }
 
-    pos[left..=right].to_vec()
+    left..right + 1
 }
 
 #[allow(clippy::naive_bytecount)]
@@ -263,11 +286,27 @@ mod tests {
         let mut matches = match_pattern(&pos, pattern, text);
         matches.sort_unstable();
 
-        let matches_correct = vec![];
+        let matches_correct: Vec<usize> = vec![];
 
         assert_eq!(matches, matches_correct);
     }
 
+    #[test]
+    fn test_match_pattern_interval() {
+        let text = b"gccttaacattattacgccta\0";
+        let pos = vec![
+            21, 20, 5, 6, 14, 11, 8, 7, 17, 1, 15, 18, 2, 16, 0, 19, 4, 13, 10, 3, 12, 9,
+        ];
+        let bwt_vec = b"attattcaggaccc\0ctttcaa";
+
+        assert_eq!(match_pattern_interval(&pos, b"tta", text), 19..22);
+        assert_eq!(
+            match_pattern_bwt_interval(&occ(bwt_vec), &less(bwt_vec), b"tta"),
+            19..22
+        );
+        assert!(match_pattern_interval(&pos, b"abc", text).is_empty());
+    }
+
     #[test]
     fn test_match_pattern_bwt() {
         // Text: gccttaacattattacgccta\0
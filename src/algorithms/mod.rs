@@ -2,4 +2,5 @@ pub mod approximative;
 pub mod dfa;
 pub mod full_text_indices;
 pub mod multiple_patterns;
+pub mod occurrences;
 pub mod single_pattern;
@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use crate::algorithms::occurrences::{Occurrences, QueryMode};
+
 /// A struct that manages all nodes in a Trie used by the Aho-Corasick algorithm.
 struct ACTrie {
     nodes: Vec<ACNode>,
@@ -182,16 +184,33 @@ impl ACTrie {
 
     /// Runs the Aho-Corasick algorithm given a list of patterns and a text.
     ///
-    /// The Trie must have already been built.
-    fn ac_with_automaton(&self, patterns: &[Vec<u8>], text: &[u8]) -> Vec<Vec<usize>> {
-        let mut res = vec![Vec::new(); patterns.len()];
+    /// The Trie must have already been built. The search stops as soon as
+    /// the queries of all patterns have been answered.
+    fn ac_with_automaton(
+        &self,
+        patterns: &[Vec<u8>],
+        text: &[u8],
+        mode: QueryMode,
+    ) -> Vec<Occurrences> {
+        let mut res: Vec<Occurrences> = patterns.iter().map(|_| Occurrences::new(mode)).collect();
+        let mut unanswered = patterns.len();
         let mut q = self.root().id;
 
         for (i, c) in text.iter().enumerate() {
             q = self.delta(q, *c);
 
             for x in self.node(q).out.iter() {
-                res[*x].push((i as isize - patterns[*x].len() as isize + 1) as usize);
+                if res[*x].answered() {
+                    continue;
+                }
+
+                if !res[*x].report((i as isize - patterns[*x].len() as isize + 1) as usize) {
+                    unanswered -= 1;
+
+                    if unanswered == 0 {
+                        return res;
+                    }
+                }
             }
         }
 
@@ -218,15 +237,15 @@ impl ACNode {
 
 /// Returns occurrences of given patterns in a text.
 ///
-/// Takes multiple patterns and a text, returning a vector containing
-/// vectors with the positions of the found occurrences for each pattern.
+/// Takes multiple patterns and a text, returning the occurrences of each
+/// pattern collected according to the given query mode.
 ///
 /// It uses the Aho-Corasick algorithm to first build a Trie with lps-links and
 /// then find the occurrences of the given patterns in the text.
-pub fn aho_corasick(patterns: &[Vec<u8>], text: &[u8]) -> Vec<Vec<usize>> {
+pub fn aho_corasick(patterns: &[Vec<u8>], text: &[u8], mode: QueryMode) -> Vec<Occurrences> {
     let ac_trie = ACTrie::new(patterns);
 
-    ac_trie.ac_with_automaton(patterns, text)
+    ac_trie.ac_with_automaton(patterns, text, mode)
 }
 
 #[cfg(test)]
@@ -243,7 +262,10 @@ mod tests {
             b"abc".to_vec(),
         ];
 
-        let matches = aho_corasick(&patterns, text);
+        let matches: Vec<Vec<usize>> = aho_corasick(&patterns, text, QueryMode::All)
+            .into_iter()
+            .map(Occurrences::into_positions)
+            .collect();
 
         let matches_correct = vec![vec![3, 9, 12], vec![7], vec![0, 16], vec![]];
 
@@ -1,31 +1,42 @@
 pub mod aho_corasick;
 pub mod naive;
 
-use std::time::SystemTime;
-
 use crate::cli::CLIParams;
 use crate::match_algorithm::MultiplePatternAlgorithm;
-use crate::measure::measurement::SingleMeasurement;
+use crate::measure::measurement::{measure_phase, SingleMeasurement};
 use crate::measure::MultiplePatternMeasure;
 
 impl MultiplePatternMeasure for MultiplePatternAlgorithm {
     /// A function to measure the runtime of an algorithm.
     ///
-    /// It takes a `pattern` and a `text` and executes a function `f` using
-    /// the standard signature of the pattern matching algorithms
-    /// `(&[u8], &[u8]) -> Vec<usize>`.
+    /// It takes multiple `patterns` and a `text` and answers the query given
+    /// by the CLI parameters for each pattern.
     ///
-    /// It returns a `Duration`, the runtime of the execution given function.
+    /// The number of matches is the total over all patterns.
     #[cfg(not(tarpaulin_include))]
-    fn measure(&self, patterns: &[Vec<u8>], text: &[u8], _: &CLIParams) -> SingleMeasurement {
-        let before = SystemTime::now();
-
-        let matches = self(patterns, text).len();
+    fn measure(
+        &self,
+        patterns: &[Vec<u8>],
+        text: &[u8],
+        cli_params: &CLIParams,
+    ) -> SingleMeasurement {
+        // Unwrapping is safe here because of the checks done in cli_params.valid()
+        let query_mode = cli_params.query_mode.unwrap();
 
-        let duration = before.elapsed();
+        let (matches, algorithm) = measure_phase(cli_params, || {
+            self(patterns, text, query_mode)
+                .iter()
+                .map(|x| x.count())
+                .sum()
+        });
 
         // Because these algorithms do not have a preparation phase the runtime
-        // of which could be measured, the first value is simply None
-        (None, duration.expect("Could not measure time."), matches)
+        // of which could be measured, the preparation is simply None
+        SingleMeasurement {
+            preparation: None,
+            algorithm,
+            matches,
+            counts: None,
+        }
     }
 }
@@ -1,17 +1,22 @@
-use crate::algorithms::single_pattern::naive::naive_all;
+use crate::algorithms::occurrences::{Occurrences, QueryMode};
+use crate::algorithms::single_pattern::naive::Naive;
+use crate::algorithms::single_pattern::PreparedSearcher;
 
 /// Returns occurrences of given patterns in a text.
 ///
-/// Takes multiple patterns and a text, returning a vector containing
-/// vectors with the positions of the found occurrences for each pattern.
+/// Takes multiple patterns and a text, returning the occurrences of each
+/// pattern collected according to the given query mode.
 ///
 /// It uses a naive approach by simply calling the naive single pattern algorithm
 /// for each given pattern.
-pub fn naive_multiple(patterns: &[Vec<u8>], text: &[u8]) -> Vec<Vec<usize>> {
+pub fn naive_multiple(patterns: &[Vec<u8>], text: &[u8], mode: QueryMode) -> Vec<Occurrences> {
     let mut matches = Vec::new();
 
     for pattern in patterns {
-        matches.push(naive_all(&pattern, text));
+        let mut occurrences = Occurrences::new(mode);
+        Naive::new(pattern).find(text, &mut occurrences);
+
+        matches.push(occurrences);
     }
 
     matches
@@ -31,7 +36,10 @@ mod tests {
             b"abc".to_vec(),
         ];
 
-        let matches = naive_multiple(&patterns, text);
+        let matches: Vec<Vec<usize>> = naive_multiple(&patterns, text, QueryMode::All)
+            .into_iter()
+            .map(Occurrences::into_positions)
+            .collect();
 
         let matches_correct = vec![vec![3, 9, 12], vec![7], vec![0, 16], vec![]];
 
@@ -0,0 +1,211 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// What a query of a pattern in a text asks for.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryMode {
+    /// The number of occurrences, without storing their positions.
+    Count,
+    /// The position of the first occurrence found.
+    First,
+    /// Whether there is any occurrence at all.
+    Exists,
+    /// The positions of all occurrences.
+    All,
+}
+
+impl FromStr for QueryMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "count" => Ok(Self::Count),
+            "first" => Ok(Self::First),
+            "exists" => Ok(Self::Exists),
+            "all" => Ok(Self::All),
+            _ => Err(format!("unknown query mode `{}`", s)),
+        }
+    }
+}
+
+/// The occurrences of a pattern reported by an algorithm, collected
+/// according to a query mode.
+///
+/// Algorithms report each occurrence as soon as they find it and stop
+/// searching once the query has been answered, so that `first` and `exists`
+/// queries don't scan the rest of the text and only `all` queries allocate
+/// memory for the positions.
+///
+/// An occurrence is usually given by its position in the text, approximative
+/// algorithms also report the error of each occurrence.
+#[derive(Debug)]
+pub struct Occurrences<T = usize> {
+    mode: QueryMode,
+    count: usize,
+    positions: Vec<T>,
+}
+
+impl<T> Occurrences<T> {
+    pub fn new(mode: QueryMode) -> Self {
+        Self {
+            mode,
+            count: 0,
+            positions: Vec::new(),
+        }
+    }
+
+    /// Reports an occurrence.
+    ///
+    /// It returns whether the algorithm has to go on searching, i. e. `false`
+    /// once the query has been answered.
+    #[inline]
+    pub fn report(&mut self, occurrence: T) -> bool {
+        match self.mode {
+            QueryMode::Count => {
+                self.count += 1;
+
+                true
+            }
+            QueryMode::All => {
+                self.count += 1;
+                self.positions.push(occurrence);
+
+                true
+            }
+            QueryMode::First | QueryMode::Exists => {
+                // Later occurrences are ignored, in case an algorithm reports
+                // them anyway
+                if self.count == 0 && self.mode == QueryMode::First {
+                    self.positions.push(occurrence);
+                }
+
+                self.count = 1;
+
+                false
+            }
+        }
+    }
+
+    /// Returns whether the query has been answered, so that no further
+    /// occurrences are needed.
+    #[inline]
+    pub fn answered(&self) -> bool {
+        matches!(self.mode, QueryMode::First | QueryMode::Exists) && self.count > 0
+    }
+
+    /// Returns the number of occurrences reported, which is at most one for
+    /// `first` and `exists` queries.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the stored occurrences, which are all occurrences for `all`
+    /// queries, the first one for `first` queries and none otherwise.
+    #[cfg(test)]
+    pub fn into_positions(self) -> Vec<T> {
+        self.positions
+    }
+}
+
+impl Occurrences {
+    /// Reports the occurrences given by an interval of a suffix array.
+    ///
+    /// Count and exists queries are answered by the size of the interval
+    /// alone, so the positions of the occurrences aren't read from the suffix
+    /// array in that case.
+    pub fn report_suffixes(&mut self, suffixes: &[usize]) {
+        match self.mode {
+            QueryMode::Count => self.count += suffixes.len(),
+            QueryMode::All => {
+                self.count += suffixes.len();
+                self.positions.extend_from_slice(suffixes);
+            }
+            QueryMode::First | QueryMode::Exists => {
+                if let Some(suffix) = suffixes.first() {
+                    self.report(*suffix);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reports the given occurrences until the query has been answered.
+    fn report_all(mode: QueryMode, occurrences: &[usize]) -> Occurrences {
+        let mut result = Occurrences::new(mode);
+
+        for occurrence in occurrences {
+            if !result.report(*occurrence) {
+                break;
+            }
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_occurrences() {
+        let occurrences = [3, 9, 12];
+
+        let all = report_all(QueryMode::All, &occurrences);
+        assert_eq!(all.count(), 3);
+        assert_eq!(all.into_positions(), vec![3, 9, 12]);
+
+        let count = report_all(QueryMode::Count, &occurrences);
+        assert_eq!(count.count(), 3);
+        assert!(count.into_positions().is_empty());
+
+        let first = report_all(QueryMode::First, &occurrences);
+        assert!(first.answered());
+        assert_eq!(first.count(), 1);
+        assert_eq!(first.into_positions(), vec![3]);
+
+        let exists = report_all(QueryMode::Exists, &occurrences);
+        assert_eq!(exists.count(), 1);
+        assert!(exists.into_positions().is_empty());
+
+        assert!(!report_all(QueryMode::Exists, &[]).answered());
+    }
+
+    #[test]
+    fn test_occurrences_reported_after_answer() {
+        let mut first = Occurrences::new(QueryMode::First);
+
+        assert!(!first.report(5));
+        assert!(!first.report(7));
+        assert_eq!(first.count(), 1);
+        assert_eq!(first.into_positions(), vec![5]);
+    }
+
+    #[test]
+    fn test_report_suffixes() {
+        let mut all = Occurrences::new(QueryMode::All);
+        all.report_suffixes(&[12, 3, 9]);
+        assert_eq!(all.count(), 3);
+        assert_eq!(all.into_positions(), vec![12, 3, 9]);
+
+        let mut count = Occurrences::new(QueryMode::Count);
+        count.report_suffixes(&[12, 3, 9]);
+        assert_eq!(count.count(), 3);
+
+        let mut first = Occurrences::new(QueryMode::First);
+        first.report_suffixes(&[12, 3, 9]);
+        assert_eq!(first.into_positions(), vec![12]);
+
+        let mut exists = Occurrences::new(QueryMode::Exists);
+        exists.report_suffixes(&[]);
+        assert_eq!(exists.count(), 0);
+    }
+
+    #[test]
+    fn test_query_mode_from_str() {
+        assert_eq!("count".parse(), Ok(QueryMode::Count));
+        assert_eq!("all".parse(), Ok(QueryMode::All));
+        assert!("any".parse::<QueryMode>().is_err());
+    }
+}
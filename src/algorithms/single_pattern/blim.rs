@@ -1,5 +1,9 @@
 use std::cmp::min;
 
+use crate::algorithms::occurrences::Occurrences;
+use crate::algorithms::single_pattern::PreparedSearcher;
+use crate::measure::counters;
+
 const WORD_SIZE: usize = usize::BITS as usize;
 
 fn compute_mask_matrix(pattern: &[u8]) -> Vec<Vec<usize>> {
@@ -47,52 +51,75 @@ fn compute_scan_order(m: usize, ws: usize) -> Vec<usize> {
     scan_order
 }
 
-pub fn blim(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let m = pattern.len();
-    let n = text.len();
-    let mut ws = min(WORD_SIZE + m - 1, n);
-    let mask = compute_mask_matrix(pattern);
-    let shift = compute_shift_vector(pattern);
-    let mut scan_order = compute_scan_order(m, ws);
+/// The Bit-Parallel Length Independent Matching algorithm (BLIM) with
+/// already computed mask matrix and shift vector.
+pub struct Blim {
+    m: usize,
+    mask: Vec<Vec<usize>>,
+    shift: Vec<usize>,
+}
 
-    let mut res = Vec::new();
-    let mut i = 0;
-    let mut flag;
+impl Blim {
+    /// Computes the mask matrix and the shift vector for the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        Self {
+            m: pattern.len(),
+            mask: compute_mask_matrix(pattern),
+            shift: compute_shift_vector(pattern),
+        }
+    }
+}
 
-    while i < n {
-        flag = mask[text[i + scan_order[0]] as usize][scan_order[0]];
+impl PreparedSearcher for Blim {
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        let m = self.m;
+        let n = text.len();
+        let mask = &self.mask;
+        let shift = &self.shift;
+        let mut ws = min(WORD_SIZE + m - 1, n);
+        let mut scan_order = compute_scan_order(m, ws);
 
-        for j in 1..ws {
-            flag &= mask[text[i + scan_order[j]] as usize][scan_order[j]];
+        let mut i = 0;
+        let mut flag;
 
-            if flag == 0 {
-                break;
+        while i < n {
+            flag = mask[counters::inspect(text[i + scan_order[0]]) as usize][scan_order[0]];
+
+            for j in 1..ws {
+                flag &= mask[counters::inspect(text[i + scan_order[j]]) as usize][scan_order[j]];
+
+                if flag == 0 {
+                    break;
+                }
             }
-        }
 
-        if flag != 0 {
-            for j in 0..min(WORD_SIZE, n - i) {
-                if flag & (1 << j) != 0 {
-                    res.push(i + j);
+            if flag != 0 {
+                for j in 0..min(WORD_SIZE, n - i) {
+                    if flag & (1 << j) != 0 && !occurrences.report(i + j) {
+                        return;
+                    }
                 }
             }
-        }
 
-        if i + ws < n {
-            i += shift[text[i + ws] as usize];
-        } else {
-            break;
-        }
+            if i + ws < n {
+                i += counters::shift(shift[counters::inspect(text[i + ws]) as usize]);
+            } else {
+                break;
+            }
 
-        // Check if the new search window still can have size ws,
-        // otherwise make it smaller
-        if n - i < WORD_SIZE + m - 1 {
-            ws = n - i;
-            scan_order = compute_scan_order(m, ws);
+            // Check if the new search window still can have size ws,
+            // otherwise make it smaller
+            if n - i < WORD_SIZE + m - 1 {
+                ws = n - i;
+                scan_order = compute_scan_order(m, ws);
+            }
         }
     }
+}
 
-    res
+/// Returns a prepared BLIM searcher for the given pattern.
+pub fn blim_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(Blim::new(pattern))
 }
 
 #[cfg(test)]
@@ -117,7 +144,7 @@ mod tests {
         let text = b"gccttaacattattacgcctagccttaacattattacgcctagccttaacattattacgcctagcccgaatta";
         let pattern = b"tta";
 
-        let mut matches = blim(pattern, text);
+        let mut matches = blim_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![3, 9, 12, 24, 30, 33, 45, 51, 54, 70];
@@ -131,7 +158,7 @@ mod tests {
             b"gccttaacattatcattattacgcctagccttaacattatttacgcctagccttaacattattacgcctagctcctcga";
         let pattern = b"gctcctcga";
 
-        let mut matches = blim(pattern, text);
+        let mut matches = blim_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![70];
@@ -144,7 +171,7 @@ mod tests {
         let text = b"ggctcctcgaatcattattacgccgctcctcgaaa";
         let pattern = b"gctcctcga";
 
-        let mut matches = blim(pattern, text);
+        let mut matches = blim_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![1, 24];
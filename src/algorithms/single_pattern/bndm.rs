@@ -1,44 +1,76 @@
+use crate::algorithms::occurrences::Occurrences;
 use crate::algorithms::single_pattern::shift_and::shift_and_single_masks;
+use crate::algorithms::single_pattern::PreparedSearcher;
+use crate::measure::counters;
+
+/// The Backward Nondeterministic DAWG Matching algorithm (BNDM) with
+/// already generated shift masks.
+pub struct Bndm {
+    m: usize,
+    masks: Vec<usize>,
+    accept: usize,
+}
+
+impl Bndm {
+    /// Generates the shift masks for the given pattern.
+    ///
+    /// It uses the same function to generate shift masks as the Shift-And
+    /// algorithm, but reverses the pattern before passing it to the mentioned
+    /// function.
+    pub fn new(pattern: &[u8]) -> Self {
+        let mut pattern_rev = pattern.to_vec();
+        pattern_rev.reverse();
+
+        let (masks, _, accept) = shift_and_single_masks(&pattern_rev);
+
+        Self {
+            m: pattern.len(),
+            masks,
+            accept,
+        }
+    }
+}
 
-/// An implementation of the Backward Nondeterministic DAWG Matching
-/// algorithm (BNDM).
-///
-/// It uses the same function to generate shift masks as the Shift-And algorithm,
-/// but reverses the pattern before passing it to the mentioned function.
-///
-/// After generating the shift masks, this function calls the actual
-/// BNDM algorithm function.
-pub fn bndm(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let mut pattern_rev = pattern.to_vec();
-    pattern_rev.reverse();
-
-    let (masks, _, accept) = shift_and_single_masks(&pattern_rev);
-
-    bndm_with_masks(text, &masks, accept, pattern.len())
+impl PreparedSearcher for Bndm {
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        bndm_with_masks(text, &self.masks, self.accept, self.m, occurrences)
+    }
+}
+
+/// Returns a prepared BNDM searcher for the given pattern.
+pub fn bndm_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(Bndm::new(pattern))
 }
 
 /// An implementation of the Backward Nondeterminstic DAWG Matching
 /// algorithm (BNDM) using already prepared shift masks.
-fn bndm_with_masks(text: &[u8], masks: &[usize], accept: usize, m: usize) -> Vec<usize> {
+fn bndm_with_masks(
+    text: &[u8],
+    masks: &[usize],
+    accept: usize,
+    m: usize,
+    occurrences: &mut Occurrences,
+) {
     let n = text.len();
     let mut window: usize = m;
     let mut active: usize;
     let mut j: usize;
     let mut lastsuffix: usize;
 
-    let mut matches: Vec<usize> = Vec::new();
-
     while window <= n {
         active = (1 << m) - 1;
         j = 1;
         lastsuffix = 0;
 
         while active != 0 {
-            active &= masks[text[window - j] as usize];
+            active &= masks[counters::inspect(text[window - j]) as usize];
 
             if active & accept != 0 {
                 if j == m {
-                    matches.push(window - m);
+                    if !occurrences.report(window - m) {
+                        return;
+                    }
+
                     break;
                 } else {
                     lastsuffix = j;
@@ -49,10 +81,8 @@ fn bndm_with_masks(text: &[u8], masks: &[usize], accept: usize, m: usize) -> Vec
             active <<= 1;
         }
 
-        window += m - lastsuffix;
+        window += counters::shift(m - lastsuffix);
     }
-
-    matches
 }
 
 #[cfg(test)]
@@ -64,7 +94,7 @@ mod tests {
         let text = b"gccttaacattattacgccta";
         let pattern = b"tta";
 
-        let mut matches = bndm(pattern, text);
+        let mut matches = bndm_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![3, 9, 12];
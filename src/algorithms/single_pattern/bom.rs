@@ -1,35 +1,61 @@
 use std::collections::HashMap;
 
-/// The Backward Oracle Matching algorithm (BOM).
-pub fn bom(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let delta = bom_delta_table(pattern);
+use crate::algorithms::occurrences::Occurrences;
+use crate::algorithms::single_pattern::PreparedSearcher;
+use crate::measure::counters;
+
+/// The Backward Oracle Matching algorithm (BOM) with an already built
+/// delta table of the factor oracle.
+pub struct Bom {
+    m: usize,
+    delta: HashMap<(usize, u8), usize>,
+}
+
+impl Bom {
+    /// Builds the delta table of the factor oracle for the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        Self {
+            m: pattern.len(),
+            delta: bom_delta_table(pattern),
+        }
+    }
+}
 
-    bom_with_delta(text, &delta, pattern.len())
+impl PreparedSearcher for Bom {
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        bom_with_delta(text, &self.delta, self.m, occurrences)
+    }
+}
+
+/// Returns a prepared BOM searcher for the given pattern.
+pub fn bom_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(Bom::new(pattern))
 }
 
-fn bom_with_delta(text: &[u8], delta: &HashMap<(usize, u8), usize>, m: usize) -> Vec<usize> {
+fn bom_with_delta(
+    text: &[u8],
+    delta: &HashMap<(usize, u8), usize>,
+    m: usize,
+    occurrences: &mut Occurrences,
+) {
     let n = text.len();
     let mut window = m;
 
-    let mut matches: Vec<usize> = Vec::new();
-
     while window <= n {
         let mut q = Some(&0);
         let mut j = 1;
 
         while j <= m && q.is_some() {
-            q = delta.get(&(*q.unwrap(), text[window - j]));
+            q = delta.get(&(*q.unwrap(), counters::inspect(text[window - j])));
             j += 1;
         }
 
-        if q.is_some() {
-            matches.push(window - m);
+        if q.is_some() && !occurrences.report(window - m) {
+            return;
         }
 
-        window += (m as isize - j as isize + 2) as usize;
+        window += counters::shift((m as isize - j as isize + 2) as usize);
     }
-
-    matches
 }
 
 fn bom_delta_table(pattern: &[u8]) -> HashMap<(usize, u8), usize> {
@@ -66,7 +92,7 @@ mod tests {
         let text = b"gccttaacattattacgccta";
         let pattern = b"tta";
 
-        let mut matches = bom(pattern, text);
+        let mut matches = bom_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![3, 9, 12];
@@ -1,5 +1,9 @@
 use std::{cmp::max, cmp::min, usize};
 
+use crate::algorithms::occurrences::Occurrences;
+use crate::algorithms::single_pattern::PreparedSearcher;
+use crate::measure::counters;
+
 /// Calcualtes the table of suffixes of string x with length m
 /// According to "Algorithms on Strings, Chapter 3.3"
 pub fn suffixes(pattern: &[u8]) -> Vec<usize> {
@@ -80,120 +84,205 @@ pub fn per(pattern: &[u8]) -> usize {
     }
 }
 
-/// modified w_memoryless_suffix_search form "Algorithms on Strings, Chapter 3"
-pub fn weak_boyer_moore_all(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let m = pattern.len();
-    let n = text.len();
-
-    let good_suff = good_suffixes(pattern);
-    let mut j = m - 1;
-
-    let mut matches = Vec::new();
-    while j < n {
-        let mut i = (m - 1) as isize;
+/// The weak Boyer-Moore algorithm with already computed good suffix table
+/// and period of the pattern.
+pub struct WeakBoyerMoore {
+    pattern: Vec<u8>,
+    good_suff: Vec<usize>,
+    period: usize,
+}
 
-        while i >= 0 && pattern[i as usize] == text[j + 1 + i as usize - m] {
-            i -= 1;
+impl WeakBoyerMoore {
+    /// Computes the good suffix table and the period of the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+            good_suff: good_suffixes(pattern),
+            period: per(pattern),
         }
+    }
+}
 
-        if i < 0 {
-            matches.push(j + 1 - pattern.len());
-        }
+impl PreparedSearcher for WeakBoyerMoore {
+    /// modified w_memoryless_suffix_search form "Algorithms on Strings, Chapter 3"
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        let pattern = &self.pattern;
+        let m = pattern.len();
+        let n = text.len();
+
+        let good_suff = &self.good_suff;
+        let mut j = m - 1;
+
+        while j < n {
+            let mut i = (m - 1) as isize;
+
+            while i >= 0
+                && counters::compare(
+                    pattern[i as usize],
+                    counters::inspect(text[j + 1 + i as usize - m]),
+                )
+            {
+                i -= 1;
+            }
 
-        if i < 0 {
-            j += per(pattern);
-        } else {
-            j += good_suff[i as usize];
+            if i < 0 && !occurrences.report(j + 1 - pattern.len()) {
+                return;
+            }
+
+            if i < 0 {
+                j += counters::shift(self.period);
+            } else {
+                j += counters::shift(good_suff[i as usize]);
+            }
         }
     }
+}
 
-    matches
+/// Returns a prepared weak Boyer-Moore searcher for the given pattern.
+pub fn weak_boyer_moore_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(WeakBoyerMoore::new(pattern))
 }
 
-/// modified w_memoryless_suffix_search form "Algorithms on Strings, Chapter 3"
-pub fn weak_memorizing_boyer_moore_all(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let m = pattern.len();
-    let n = text.len();
+/// The weak memorizing Boyer-Moore algorithm with already computed good suffix
+/// table and period of the pattern.
+pub struct WeakMemorizingBoyerMoore {
+    pattern: Vec<u8>,
+    good_suff: Vec<usize>,
+    period: usize,
+}
 
-    let good_suff = good_suffixes(pattern);
-    let mut j = m - 1;
-    let mut shift: usize = 0;
-    let mut mem: usize = 0;
+impl WeakMemorizingBoyerMoore {
+    /// Computes the good suffix table and the period of the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+            good_suff: good_suffixes(pattern),
+            period: per(pattern),
+        }
+    }
+}
 
-    let mut matches = Vec::new();
+impl PreparedSearcher for WeakMemorizingBoyerMoore {
+    /// modified w_memoryless_suffix_search form "Algorithms on Strings, Chapter 3"
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        let pattern = &self.pattern;
+        let m = pattern.len();
+        let n = text.len();
+
+        let good_suff = &self.good_suff;
+        let mut j = m - 1;
+        let mut shift: usize = 0;
+        let mut mem: usize = 0;
+
+        while j < n {
+            let mut i = (m - 1) as isize;
+
+            while i >= 0
+                && counters::compare(
+                    pattern[i as usize],
+                    counters::inspect(text[j + 1 + i as usize - m]),
+                )
+            {
+                if i as usize == m - shift && mem > 0 {
+                    i = i - (mem as isize) - 1; // Jump
+                } else {
+                    i -= 1;
+                }
+            }
 
-    while j < n {
-        let mut i = (m - 1) as isize;
+            if i < 0 && !occurrences.report(j + 1 - pattern.len()) {
+                return;
+            }
 
-        while i >= 0 && pattern[i as usize] == text[j + 1 + i as usize - m] {
-            if i as usize == m - shift && mem > 0 {
-                i = i - (mem as isize) - 1; // Jump
+            if i < 0 {
+                shift = self.period;
+                mem = m - shift;
             } else {
-                i -= 1;
+                shift = good_suff[i as usize];
+                mem = min(m - shift, m - 1 - (i as usize));
             }
+            j += counters::shift(shift);
         }
-
-        if i < 0 {
-            matches.push(j + 1 - pattern.len());
-        }
-
-        if i < 0 {
-            shift = per(pattern);
-            mem = m - shift;
-        } else {
-            shift = good_suff[i as usize];
-            mem = min(m - shift, m - 1 - (i as usize));
-        }
-        j += shift;
     }
-
-    matches
 }
 
-/// modified turbo_suffix_search_good_suff form "Algorithms on Strings, Chapter 3"
-pub fn weak_turbo_boyer_moore_all(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let m = pattern.len();
-    let n = text.len();
-
-    let good_suff = good_suffixes(pattern);
-    let mut shift: usize = 0;
-    let mut mem: usize = 0;
-    let mut j = m - 1;
+/// Returns a prepared weak memorizing Boyer-Moore searcher for the given pattern.
+pub fn weak_memorizing_boyer_moore_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(WeakMemorizingBoyerMoore::new(pattern))
+}
 
-    let mut matches = Vec::new();
+/// The weak turbo Boyer-Moore algorithm with already computed good suffix
+/// table and period of the pattern.
+pub struct WeakTurboBoyerMoore {
+    pattern: Vec<u8>,
+    good_suff: Vec<usize>,
+    period: usize,
+}
 
-    while j < n {
-        let mut i = (m - 1) as isize;
+impl WeakTurboBoyerMoore {
+    /// Computes the good suffix table and the period of the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+            good_suff: good_suffixes(pattern),
+            period: per(pattern),
+        }
+    }
+}
 
-        while i >= 0 && pattern[i as usize] == text[j + 1 + i as usize - m] {
-            if i as usize == m - shift {
-                i = i - (mem as isize) - 1; // Jump
-            } else {
-                i -= 1;
+impl PreparedSearcher for WeakTurboBoyerMoore {
+    /// modified turbo_suffix_search_good_suff form "Algorithms on Strings, Chapter 3"
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        let pattern = &self.pattern;
+        let m = pattern.len();
+        let n = text.len();
+
+        let good_suff = &self.good_suff;
+        let mut shift: usize = 0;
+        let mut mem: usize = 0;
+        let mut j = m - 1;
+
+        while j < n {
+            let mut i = (m - 1) as isize;
+
+            while i >= 0
+                && counters::compare(
+                    pattern[i as usize],
+                    counters::inspect(text[j + 1 + i as usize - m]),
+                )
+            {
+                if i as usize == m - shift {
+                    i = i - (mem as isize) - 1; // Jump
+                } else {
+                    i -= 1;
+                }
             }
-        }
 
-        if i < 0 {
-            matches.push(j + 1 - pattern.len());
-        }
+            if i < 0 && !occurrences.report(j + 1 - pattern.len()) {
+                return;
+            }
 
-        if i < 0 {
-            shift = per(pattern);
-            mem = m - shift;
-        } else {
-            let turbo: isize = (mem as isize) + 1 + i - (m as isize);
-            if turbo <= (good_suff[i as usize] as isize) {
-                shift = good_suff[i as usize];
-                mem = min(m - shift, m - 1 - (i as usize));
+            if i < 0 {
+                shift = self.period;
+                mem = m - shift;
             } else {
-                shift = max(turbo, (m as isize) - 1 - i) as usize;
-                mem = 0;
+                let turbo: isize = (mem as isize) + 1 + i - (m as isize);
+                if turbo <= (good_suff[i as usize] as isize) {
+                    shift = good_suff[i as usize];
+                    mem = min(m - shift, m - 1 - (i as usize));
+                } else {
+                    shift = max(turbo, (m as isize) - 1 - i) as usize;
+                    mem = 0;
+                }
             }
+            j += counters::shift(shift); // Shift
         }
-        j += shift; // Shift
     }
+}
 
-    matches
+/// Returns a prepared weak turbo Boyer-Moore searcher for the given pattern.
+pub fn weak_turbo_boyer_moore_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(WeakTurboBoyerMoore::new(pattern))
 }
 
 #[cfg(test)]
@@ -217,7 +306,7 @@ mod tests {
         let suff = suffixes(pattern);
 
         assert_eq!(suff.len(), pattern.len());
-        assert_eq!(suff, vec![]);
+        assert_eq!(suff, Vec::<usize>::new());
     }
 
     #[test]
@@ -271,7 +360,7 @@ mod tests {
         let good_suff = good_suffixes(pattern);
 
         assert_eq!(good_suff.len(), pattern.len());
-        assert_eq!(good_suff, vec![]);
+        assert_eq!(good_suff, Vec::<usize>::new());
     }
 
     #[test]
@@ -317,7 +406,7 @@ mod tests {
         // Example from Algorithms on Strings chapter 3.5 Figure 3.14 (b)
         let text = b"aaacabaaacabacaaacababa";
         let pattern = b"aaacababa";
-        let matches = weak_boyer_moore_all(pattern, text);
+        let matches = weak_boyer_moore_prepare(pattern).search(text);
 
         assert_eq!(matches, vec![14]);
     }
@@ -326,7 +415,7 @@ mod tests {
     fn test_weak_bm_good_suff_simple() {
         let text = b"abcaaabcabcabc";
         let pattern = b"abc";
-        let matches = weak_boyer_moore_all(pattern, text);
+        let matches = weak_boyer_moore_prepare(pattern).search(text);
 
         assert_eq!(matches, vec![0, 5, 8, 11]);
     }
@@ -336,7 +425,7 @@ mod tests {
         // Example from Algorithms on Strings chapter 3.5 Figure 3.14 (b)
         let text = b"aaacabaaacabacaaacababa";
         let pattern = b"aaacababa";
-        let matches = weak_turbo_boyer_moore_all(pattern, text);
+        let matches = weak_turbo_boyer_moore_prepare(pattern).search(text);
 
         assert_eq!(matches, vec![14]);
     }
@@ -345,16 +434,16 @@ mod tests {
     fn test_weak_turbo_bm_good_suff_turbo_shift() {
         let text = b"CGACCGCACCCGCTCCGTCG";
         let pattern = b"TCACCCCACCC";
-        let matches = weak_turbo_boyer_moore_all(pattern, text);
+        let matches = weak_turbo_boyer_moore_prepare(pattern).search(text);
 
-        assert_eq!(matches, vec![]);
+        assert_eq!(matches, Vec::<usize>::new());
     }
 
     #[test]
     fn test_weak_turbo_bm_good_suff() {
         let text = b"aaaabaaaabaaa";
         let pattern = b"aaabaaa";
-        let matches = weak_turbo_boyer_moore_all(pattern, text);
+        let matches = weak_turbo_boyer_moore_prepare(pattern).search(text);
 
         assert_eq!(matches, vec![1, 6]);
     }
@@ -1,4 +1,7 @@
+use crate::algorithms::occurrences::Occurrences;
 use crate::algorithms::single_pattern::horspool::horspool_shift;
+use crate::algorithms::single_pattern::PreparedSearcher;
+use crate::measure::counters;
 
 fn d_table(pattern: &[u8]) -> Vec<usize> {
     let mut d = vec![pattern.len(); 256];
@@ -31,31 +34,61 @@ fn d2_table(pattern: &[u8], d: &[usize]) -> Vec<Vec<usize>> {
     d2
 }
 
-pub fn double_window(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let m = pattern.len();
-    let n = text.len();
-    let d = d_table(pattern);
-    let d2 = d2_table(pattern, &d);
-    let shift = horspool_shift(pattern);
+/// The Double Window algorithm with already computed shift tables.
+pub struct DoubleWindow {
+    pattern: Vec<u8>,
+    d2: Vec<Vec<usize>>,
+    shift: Vec<usize>,
+}
 
-    let mut res = Vec::new();
-    let mut pos = m - 1;
+impl DoubleWindow {
+    /// Computes the two-dimensional shift table and the Horspool shift table
+    /// for the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        let d = d_table(pattern);
 
-    while pos < n {
-        let r = d2[text[pos] as usize][*text.get(pos + m).unwrap_or(&pattern[0]) as usize];
+        Self {
+            pattern: pattern.to_vec(),
+            d2: d2_table(pattern, &d),
+            shift: horspool_shift(pattern),
+        }
+    }
+}
 
-        if r == 0 {
-            if &text[pos - (m - 1)..pos + 1] == pattern {
-                res.push(pos - (m - 1));
+impl PreparedSearcher for DoubleWindow {
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        let pattern = &self.pattern;
+        let m = pattern.len();
+        let n = text.len();
+        let d2 = &self.d2;
+        let shift = &self.shift;
+
+        let mut pos = m - 1;
+
+        while pos < n {
+            let next = text
+                .get(pos + m)
+                .map_or(pattern[0], |c| counters::inspect(*c));
+            let r = d2[counters::inspect(text[pos]) as usize][next as usize];
+
+            if r == 0 {
+                if counters::compare_window(&text[pos - (m - 1)..pos + 1], pattern)
+                    && !occurrences.report(pos - (m - 1))
+                {
+                    return;
+                }
+
+                pos += counters::shift(shift[text[pos] as usize]);
+            } else {
+                pos += counters::shift(r);
             }
-
-            pos += shift[text[pos] as usize];
-        } else {
-            pos += r;
         }
     }
+}
 
-    res
+/// Returns a prepared Double Window searcher for the given pattern.
+pub fn double_window_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(DoubleWindow::new(pattern))
 }
 
 fn d2_table_alt(pattern: &[u8], shift: &[usize], d: &[usize]) -> Vec<Vec<usize>> {
@@ -77,27 +110,56 @@ fn d2_table_alt(pattern: &[u8], shift: &[usize], d: &[usize]) -> Vec<Vec<usize>>
     d2
 }
 
-pub fn double_window_alt(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let m = pattern.len();
-    let n = text.len();
-    let shift = horspool_shift(pattern);
-    let d = d_table(pattern);
-    let d2 = d2_table_alt(pattern, &shift, &d);
-
-    let mut res = Vec::new();
-    let mut pos = m - 1;
+/// The alternative Double Window algorithm with an already computed
+/// two-dimensional shift table.
+pub struct DoubleWindowAlt {
+    pattern: Vec<u8>,
+    d2: Vec<Vec<usize>>,
+}
 
-    while pos < n {
-        let r = d2[text[pos] as usize][*text.get(pos + m).unwrap_or(&pattern[0]) as usize];
+impl DoubleWindowAlt {
+    /// Computes the two-dimensional shift table for the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        let shift = horspool_shift(pattern);
+        let d = d_table(pattern);
 
-        if pattern[m - 1] == text[pos] && &text[pos - (m - 1)..pos + 1] == pattern {
-            res.push(pos - (m - 1));
+        Self {
+            pattern: pattern.to_vec(),
+            d2: d2_table_alt(pattern, &shift, &d),
         }
+    }
+}
 
-        pos += r;
+impl PreparedSearcher for DoubleWindowAlt {
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        let pattern = &self.pattern;
+        let m = pattern.len();
+        let n = text.len();
+        let d2 = &self.d2;
+
+        let mut pos = m - 1;
+
+        while pos < n {
+            let next = text
+                .get(pos + m)
+                .map_or(pattern[0], |c| counters::inspect(*c));
+            let r = d2[counters::inspect(text[pos]) as usize][next as usize];
+
+            if counters::compare(pattern[m - 1], text[pos])
+                && counters::compare_window(&text[pos - (m - 1)..pos + 1], pattern)
+                && !occurrences.report(pos - (m - 1))
+            {
+                return;
+            }
+
+            pos += counters::shift(r);
+        }
     }
+}
 
-    res
+/// Returns a prepared alternative Double Window searcher for the given pattern.
+pub fn double_window_alt_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(DoubleWindowAlt::new(pattern))
 }
 
 #[cfg(test)]
@@ -109,7 +171,7 @@ mod tests {
         let text = b"gccttaacattattacgccta";
         let pattern = b"tta";
 
-        let mut matches = double_window(pattern, text);
+        let mut matches = double_window_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![3, 9, 12];
@@ -122,7 +184,7 @@ mod tests {
         let text = b"Lorem ipsum dolor sit amet, consetetur sadipscing elitr, sed diam nonumy eirmod tempor invidunt ut labore et dolore magna aliquyam erat, sed diam voluptua. At vero eos et accusam et justo duo dolores et ea rebum. Stet clita kasd gubergren, no sea takimata sanctus est Lorem ipsum dolor sit amet. Lorem ipsum dolor sit amet, consetetur sadipscing elitr, sed diam nonumy eirmod tempor invidunt ut labore et dolore magna aliquyam erat, sed diam voluptua. At vero eos et accusam et justo duo dolores et ea rebum. Stet clita kasd gubergren, no sea takimata sanctus est Lorem ipsum dolor sit amet.";
         let pattern = b"ipsum";
 
-        let mut matches = double_window(pattern, text);
+        let mut matches = double_window_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![6, 274, 302, 570];
@@ -135,7 +197,7 @@ mod tests {
         let text = b"abbeabddaaaaaaaaaaaa";
         let pattern = b"abdd";
 
-        let mut matches = double_window(pattern, text);
+        let mut matches = double_window_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![4];
@@ -148,7 +210,7 @@ mod tests {
         let text = b"gccttaacattattacgcctagccttaacattattacgcctagctcctcga";
         let pattern = b"gctcctcga";
 
-        let mut matches = double_window(pattern, text);
+        let mut matches = double_window_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![42];
@@ -161,7 +223,7 @@ mod tests {
         let text = b"Lorem ipsum dolor sit amet, consetetur sadipscing elitr, sed diam nonumy eirmod tempor invidunt ut labore et dolore magna aliquyam erat, sed diam voluptua. At vero eos et accusam et justo duo dolores et ea rebum. Stet clita kasd gubergren, no sea takimata sanctus est Lorem ipsum dolor sit amet. Lorem ipsum dolor sit amet, consetetur sadipscing elitr, sed diam nonumy eirmod tempor invidunt ut labore et dolore magna aliquyam erat, sed diam voluptua. At vero eos et accusam et justo duo dolores et ea rebum. Stet clita kasd gubergren, no sea takimata sanctus est Lorem ipsum dolor sit amet.";
         let pattern = b"ipsum";
 
-        let mut matches = double_window_alt(pattern, text);
+        let mut matches = double_window_alt_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![6, 274, 302, 570];
@@ -1,3 +1,7 @@
+use crate::algorithms::occurrences::Occurrences;
+use crate::algorithms::single_pattern::PreparedSearcher;
+use crate::measure::counters;
+
 pub fn horspool_shift(pattern: &[u8]) -> Vec<usize> {
     let mut shift = vec![pattern.len(); 256];
     let m = pattern.len();
@@ -10,46 +14,67 @@ pub fn horspool_shift(pattern: &[u8]) -> Vec<usize> {
     shift
 }
 
-pub fn horspool(pattern: &[u8], text: &[u8], i0: usize) -> Option<usize> {
+/// The Horspool algorithm with an already computed shift table.
+pub struct Horspool {
+    pattern: Vec<u8>,
+    shift: Vec<usize>,
+}
+
+impl Horspool {
+    /// Computes the shift table for the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+            shift: horspool_shift(pattern),
+        }
+    }
+}
+
+impl PreparedSearcher for Horspool {
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        let mut i0 = 0;
+
+        while let Some(occ) = horspool(&self.pattern, text, i0, &self.shift) {
+            if !occurrences.report(occ) {
+                break;
+            }
+
+            i0 = occ + counters::shift(1); // TODO or `+ m`?
+        }
+    }
+}
+
+/// Returns a prepared Horspool searcher for the given pattern.
+pub fn horspool_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(Horspool::new(pattern))
+}
+
+pub fn horspool(pattern: &[u8], text: &[u8], i0: usize, shift: &[usize]) -> Option<usize> {
     let m = pattern.len();
     let n = text.len();
 
-    let shift = horspool_shift(pattern);
     let mut last = i0 + m - 1;
     let p_last = pattern[m - 1];
 
     loop {
-        while last < n && text[last] != p_last {
-            last += shift[text[last] as usize];
+        while last < n && !counters::compare(counters::inspect(text[last]), p_last) {
+            last += counters::shift(shift[text[last] as usize]);
         }
 
         if last >= n {
             break;
         }
 
-        if text[last - (m - 1)..last] == pattern[0..m - 1] {
-            return Some(last - m + 1);
+        if counters::compare_window(&text[last - (m - 1)..last], &pattern[0..m - 1]) {
+            return Some(last + 1 - m);
         }
 
-        last += shift[p_last as usize];
+        last += counters::shift(shift[p_last as usize]);
     }
 
     None
 }
 
-pub fn horspool_all(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let mut res = Vec::new();
-    let mut i0 = 0;
-
-    while let Some(occ) = horspool(pattern, text, i0) {
-        res.push(occ);
-
-        i0 = occ + 1; // TODO or `+ m`?
-    }
-
-    res
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,7 +84,7 @@ mod tests {
         let text = b"gccttaacattattacgccta";
         let pattern = b"tta";
 
-        let mut matches = horspool_all(pattern, text);
+        let mut matches = horspool_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![3, 9, 12];
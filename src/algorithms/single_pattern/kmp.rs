@@ -1,4 +1,7 @@
 use crate::algorithms::dfa::dfa_with_lps_delta;
+use crate::algorithms::occurrences::Occurrences;
+use crate::algorithms::single_pattern::PreparedSearcher;
+use crate::measure::counters;
 
 /// Computes the lps function used by the KMP algorithm.
 ///
@@ -40,13 +43,21 @@ pub fn dfa_delta_lps(q: isize, c: u8, pattern: &[u8], lps: &[isize]) -> isize {
     let m = pattern.len();
 
     let mut q = q;
+    let c = counters::inspect(c);
 
-    while q == m as isize - 1 || (pattern[(q + 1) as usize] != c && q > -1) {
-        q = lps[q as usize] - 1;
+    while q == m as isize - 1 || (q > -1 && !counters::compare(pattern[(q + 1) as usize], c)) {
+        let next = lps[q as usize] - 1;
+
+        // The start of the pattern moves along the text by as many
+        // characters as the state falls back
+        counters::shift((q - next) as usize);
+        q = next;
     }
 
-    if pattern[(q + 1) as usize] == c {
+    if counters::compare(pattern[(q + 1) as usize], c) {
         q += 1;
+    } else {
+        counters::shift(1);
     }
 
     q
@@ -54,31 +65,51 @@ pub fn dfa_delta_lps(q: isize, c: u8, pattern: &[u8], lps: &[isize]) -> isize {
 
 /// An implementation of the Knuth-Morris-Pratt algorithm (KMP).
 ///
-/// It generates the lps function and then runs a DFA using `dfa_delta_lps`,
+/// It takes the precomputed lps function and runs a DFA using `dfa_delta_lps`,
 /// a delta function that takes the lps function to calculate states.
-pub fn kmp(pattern: &[u8], text: &[u8], i0: usize) -> Option<usize> {
-    let lps = kmp_compute_lps(pattern);
-
-    dfa_with_lps_delta(pattern, text, dfa_delta_lps, &lps, i0)
+pub fn kmp(pattern: &[u8], text: &[u8], i0: usize, lps: &[isize]) -> Option<usize> {
+    dfa_with_lps_delta(pattern, text, dfa_delta_lps, lps, i0)
 }
 
-/// Calculates all occurrences of a given pattern in a text by executing
-/// the KMP algorithm multiple times.
-///
-/// The `i0` value starts at 0 and is increased after each execution to
-/// start the next execution right after the current occurrence's position
-/// in text.
-pub fn kmp_all(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let mut res = Vec::new();
-    let mut i0 = 0;
+/// The KMP algorithm with an already computed lps function.
+pub struct Kmp {
+    pattern: Vec<u8>,
+    lps: Vec<isize>,
+}
 
-    while let Some(occ) = kmp(pattern, text, i0) {
-        res.push(occ);
+impl Kmp {
+    /// Computes the lps function for the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+            lps: kmp_compute_lps(pattern),
+        }
+    }
+}
 
-        i0 = occ + 1;
+impl PreparedSearcher for Kmp {
+    /// Reports the occurrences of the pattern in a text by executing
+    /// the KMP algorithm multiple times.
+    ///
+    /// The `i0` value starts at 0 and is increased after each execution to
+    /// start the next execution right after the current occurrence's position
+    /// in text.
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        let mut i0 = 0;
+
+        while let Some(occ) = kmp(&self.pattern, text, i0, &self.lps) {
+            if !occurrences.report(occ) {
+                break;
+            }
+
+            i0 = occ + counters::shift(1);
+        }
     }
+}
 
-    res
+/// Returns a prepared KMP searcher for the given pattern.
+pub fn kmp_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(Kmp::new(pattern))
 }
 
 /// The classic implementation of the Knuth-Morris-Pratt algorithm (KMP).
@@ -86,24 +117,32 @@ pub fn kmp_all(pattern: &[u8], text: &[u8]) -> Vec<usize> {
 /// It searches for the first occurrence of `pattern` in `text` starting at
 /// index `i0` of the text.
 ///
+/// It takes the precomputed lps function of the pattern.
+///
 /// After an occurrence has been found, the algorithm returns the index
 /// marking the first character of the occurrence and therefore terminates.
 /// If the pattern could not be found in the `text`, `None` is returned.
-pub fn kmp_classic(pattern: &[u8], text: &[u8], i0: usize) -> Option<usize> {
+pub fn kmp_classic(pattern: &[u8], text: &[u8], i0: usize, lps: &[isize]) -> Option<usize> {
     let m = pattern.len();
     let n = text.len();
 
     let mut q: isize = -1;
-    let lps = kmp_compute_lps(pattern);
 
     // Iterate over i0..n
     for (i, c) in text.iter().enumerate().take(n).skip(i0) {
-        while q == m as isize - 1 || (pattern[(q + 1) as usize] != *c && q > -1) {
-            q = lps[q as usize] - 1;
+        let c = counters::inspect(*c);
+
+        while q == m as isize - 1 || (q > -1 && !counters::compare(pattern[(q + 1) as usize], c)) {
+            let next = lps[q as usize] - 1;
+
+            counters::shift((q - next) as usize);
+            q = next;
         }
 
-        if pattern[(q + 1) as usize] == *c {
+        if counters::compare(pattern[(q + 1) as usize], c) {
             q += 1;
+        } else {
+            counters::shift(1);
         }
 
         if q == (m - 1) as isize {
@@ -114,17 +153,39 @@ pub fn kmp_classic(pattern: &[u8], text: &[u8], i0: usize) -> Option<usize> {
     None
 }
 
-pub fn kmp_classic_all(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let mut res = Vec::new();
-    let mut i0 = 0;
+/// The classic KMP algorithm with an already computed lps function.
+pub struct KmpClassic {
+    pattern: Vec<u8>,
+    lps: Vec<isize>,
+}
+
+impl KmpClassic {
+    /// Computes the lps function for the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+            lps: kmp_compute_lps(pattern),
+        }
+    }
+}
+
+impl PreparedSearcher for KmpClassic {
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        let mut i0 = 0;
 
-    while let Some(occ) = kmp_classic(pattern, text, i0) {
-        res.push(occ);
+        while let Some(occ) = kmp_classic(&self.pattern, text, i0, &self.lps) {
+            if !occurrences.report(occ) {
+                break;
+            }
 
-        i0 = occ + 1;
+            i0 = occ + counters::shift(1);
+        }
     }
+}
 
-    res
+/// Returns a prepared classic KMP searcher for the given pattern.
+pub fn kmp_classic_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(KmpClassic::new(pattern))
 }
 
 #[cfg(test)]
@@ -136,7 +197,7 @@ mod tests {
         let text = b"gccttaacattattacgccta\0";
         let pattern = b"tta";
 
-        let mut matches = kmp_all(pattern, text);
+        let mut matches = kmp_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![3, 9, 12];
@@ -149,7 +210,7 @@ mod tests {
         let text = b"gccttaacattattacgccta\0";
         let pattern = b"tta";
 
-        let mut matches = kmp_classic_all(pattern, text);
+        let mut matches = kmp_classic_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![3, 9, 12];
@@ -9,31 +9,118 @@ pub mod kmp;
 pub mod naive;
 pub mod shift_and;
 
-use std::time::SystemTime;
-
+use crate::algorithms::occurrences::Occurrences;
+#[cfg(test)]
+use crate::algorithms::occurrences::QueryMode;
 use crate::cli::CLIParams;
 use crate::match_algorithm::SinglePatternAlgorithm;
-use crate::measure::measurement::SingleMeasurement;
+use crate::measure::counters::count_operations;
+use crate::measure::measurement::{measure_phase, SingleMeasurement};
 use crate::measure::Measure;
 
+/// A single pattern algorithm which has already preprocessed its pattern
+/// and can search for it in a text.
+///
+/// The preprocessing is done when creating the searcher, so that the
+/// preparation and the search can be measured separately.
+pub trait PreparedSearcher {
+    /// Reports the occurrences of the pattern in `text` from left to right
+    /// until the query of `occurrences` has been answered.
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences);
+
+    /// Returns the positions of all occurrences of the pattern in `text`.
+    #[cfg(test)]
+    fn search(&self, text: &[u8]) -> Vec<usize> {
+        let mut occurrences = Occurrences::new(QueryMode::All);
+        self.find(text, &mut occurrences);
+
+        occurrences.into_positions()
+    }
+}
+
 impl Measure for SinglePatternAlgorithm {
     /// A function to measure the runtime of an algorithm.
     ///
-    /// It takes a `pattern` and a `text` and executes a function `f` using
-    /// the standard signature of the pattern matching algorithms
-    /// `(&[u8], &[u8]) -> Vec<usize>`.
-    ///
-    /// It returns a `Duration`, the runtime of the execution given function.
+    /// It separately measures both the preparation time, i. e. the time it
+    /// takes to preprocess the pattern and create a `PreparedSearcher`, and
+    /// the execution time, i. e. the time it takes the searcher to answer the
+    /// query given by the CLI parameters, e. g. to find all occurrences of the
+    /// pattern in the text. If the program has been built with the
+    /// `counters` feature, the operations of the searcher are counted, too.
     #[cfg(not(tarpaulin_include))]
-    fn measure(&self, pattern: &[u8], text: &[u8], _: &CLIParams) -> SingleMeasurement {
-        let before = SystemTime::now();
+    fn measure(&self, pattern: &[u8], text: &[u8], cli_params: &CLIParams) -> SingleMeasurement {
+        let (searcher, preparation) = measure_phase(cli_params, || self(pattern));
+
+        // Unwrapping is safe here because of the checks done in cli_params.valid()
+        let query_mode = cli_params.query_mode.unwrap();
+
+        let ((matches, counts), algorithm) = measure_phase(cli_params, || {
+            count_operations(|| {
+                let mut occurrences = Occurrences::new(query_mode);
+                searcher.find(text, &mut occurrences);
+
+                occurrences.count()
+            })
+        });
+
+        SingleMeasurement {
+            preparation: Some(preparation),
+            algorithm,
+            matches,
+            counts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepared_searcher_reuse() {
+        let algorithms: Vec<(&str, SinglePatternAlgorithm)> = vec![
+            ("blim", blim::blim_prepare),
+            ("bndm", bndm::bndm_prepare),
+            ("bom", bom::bom_prepare),
+            ("wbm", boyer_moore::weak_boyer_moore_prepare),
+            ("wmbm", boyer_moore::weak_memorizing_boyer_moore_prepare),
+            ("wtbm", boyer_moore::weak_turbo_boyer_moore_prepare),
+            ("dw", double_window::double_window_prepare),
+            ("dw-alt", double_window::double_window_alt_prepare),
+            ("horspool", horspool::horspool_prepare),
+            ("kmp", kmp::kmp_prepare),
+            ("kmp-classic", kmp::kmp_classic_prepare),
+            ("naive", naive::naive_prepare),
+            ("shift-and", shift_and::shift_and_prepare),
+        ];
+
+        let pattern = b"gcta";
+        let texts: [&[u8]; 4] = [
+            b"gctagctagcta",
+            b"aaaaaaaaaaaaaaaa",
+            b"tgctacgctaagcttgcta",
+            b"gcta",
+        ];
+
+        for (name, prepare) in algorithms {
+            let searcher = prepare(pattern);
 
-        let matches = self(pattern, text).len();
+            for text in texts.iter() {
+                let expected: Vec<usize> = text
+                    .windows(pattern.len())
+                    .enumerate()
+                    .filter(|(_, window)| window == pattern)
+                    .map(|(i, _)| i)
+                    .collect();
 
-        let duration = before.elapsed();
+                let mut reused = searcher.search(text);
+                let mut fresh = prepare(pattern).search(text);
+                reused.sort_unstable();
+                fresh.sort_unstable();
 
-        // Because these algorithms do not have a preparation phase the runtime
-        // of which could be measured, the first value is simply None
-        (None, duration.expect("Could not measure time."), matches)
+                assert_eq!(reused, fresh, "{}", name);
+                assert_eq!(reused, expected, "{}", name);
+            }
+        }
     }
 }
@@ -1,3 +1,7 @@
+use crate::algorithms::occurrences::Occurrences;
+use crate::algorithms::single_pattern::PreparedSearcher;
+use crate::measure::counters;
+
 /// The naive algorithm approach uses a simple loop to look for an occurrence
 /// of a `pattern` in a `text`.
 ///
@@ -13,25 +17,46 @@ pub fn naive(pattern: &[u8], text: &[u8], i0: usize) -> Option<usize> {
     let n = text.len();
 
     for i in i0..(n - m + 1) {
-        if &text[i..i + m] == pattern {
+        if counters::compare_window(&text[i..i + m], pattern) {
             return Some(i);
         }
+
+        counters::shift(1);
     }
 
     None
 }
 
-pub fn naive_all(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let mut res = Vec::new();
-    let mut i0 = 0;
+/// The naive algorithm, which doesn't preprocess its pattern.
+pub struct Naive {
+    pattern: Vec<u8>,
+}
+
+impl Naive {
+    pub fn new(pattern: &[u8]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+        }
+    }
+}
+
+impl PreparedSearcher for Naive {
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        let mut i0 = 0;
 
-    while let Some(occ) = naive(pattern, text, i0) {
-        res.push(occ);
+        while let Some(occ) = naive(&self.pattern, text, i0) {
+            if !occurrences.report(occ) {
+                break;
+            }
 
-        i0 = occ + 1;
+            i0 = occ + counters::shift(1);
+        }
     }
+}
 
-    res
+/// Returns a prepared naive searcher for the given pattern.
+pub fn naive_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(Naive::new(pattern))
 }
 
 #[cfg(test)]
@@ -43,7 +68,7 @@ mod tests {
         let text = b"gccttaacattattacgccta";
         let pattern = b"tta";
 
-        let mut matches = naive_all(pattern, text);
+        let mut matches = naive_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![3, 9, 12];
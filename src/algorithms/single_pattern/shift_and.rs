@@ -1,3 +1,7 @@
+use crate::algorithms::occurrences::Occurrences;
+use crate::algorithms::single_pattern::PreparedSearcher;
+use crate::measure::counters;
+
 /// Preparation function for the Shift-And Algorithm.
 ///
 /// For a given pattern, it returns a tuple containing:
@@ -18,38 +22,66 @@ pub fn shift_and_single_masks(pattern: &[u8]) -> (Vec<usize>, usize, usize) {
     (masks, 1, bit / 2)
 }
 
-/// Simulates the Shift-And Algorithm's NFA.
+/// Simulates the Shift-And Algorithm's NFA, reporting the start of each
+/// occurrence of a pattern of length `m`.
 fn shift_and_with_masks(
     text: &[u8],
     masks: &[usize],
     ones: usize,
     accept: usize,
-) -> Vec<(usize, usize)> {
-    let mut res = Vec::new();
+    m: usize,
+    occurrences: &mut Occurrences,
+) {
     let mut active: usize = 0;
 
     for (i, c) in text.iter().enumerate() {
-        active = ((active << 1) | ones) & masks[*c as usize];
+        active = ((active << 1) | ones) & masks[counters::inspect(*c) as usize];
 
         let found = active & accept;
-        if found != 0 {
-            res.push((i, found));
+        if found != 0 && !occurrences.report(i + 1 - m) {
+            return;
         }
     }
+}
 
-    res
+/// The Shift-And algorithm with already computed bit masks.
+pub struct ShiftAnd {
+    m: usize,
+    masks: Vec<usize>,
+    ones: usize,
+    accept: usize,
 }
 
-pub fn shift_and(pattern: &[u8], text: &[u8]) -> Vec<usize> {
-    let mut res = Vec::new();
-    let m = pattern.len();
-    let (mask, ones, accept) = shift_and_single_masks(pattern);
+impl ShiftAnd {
+    /// Computes the bit masks for the given pattern.
+    pub fn new(pattern: &[u8]) -> Self {
+        let (masks, ones, accept) = shift_and_single_masks(pattern);
 
-    for (i, _) in shift_and_with_masks(text, &mask, ones, accept) {
-        res.push(i - m + 1);
+        Self {
+            m: pattern.len(),
+            masks,
+            ones,
+            accept,
+        }
     }
+}
+
+impl PreparedSearcher for ShiftAnd {
+    fn find(&self, text: &[u8], occurrences: &mut Occurrences) {
+        shift_and_with_masks(
+            text,
+            &self.masks,
+            self.ones,
+            self.accept,
+            self.m,
+            occurrences,
+        );
+    }
+}
 
-    res
+/// Returns a prepared Shift-And searcher for the given pattern.
+pub fn shift_and_prepare(pattern: &[u8]) -> Box<dyn PreparedSearcher> {
+    Box::new(ShiftAnd::new(pattern))
 }
 
 #[cfg(test)]
@@ -61,7 +93,7 @@ mod tests {
         let text = b"gccttaacattattacgccta";
         let pattern = b"tta";
 
-        let mut matches = shift_and(pattern, text);
+        let mut matches = shift_and_prepare(pattern).search(text);
         matches.sort_unstable();
 
         let matches_correct = vec![3, 9, 12];
@@ -1,17 +1,72 @@
 use clap::{App, ArgMatches};
+use serde::{Deserialize, Serialize};
 
+use crate::algorithms::occurrences::QueryMode;
 use crate::match_algorithm::match_algorithms;
+use crate::measure::cache::CacheMode;
+use crate::measure::chart::LogAxes;
+use crate::measure::isolation::IsolatedCell;
+use crate::measure::output::OutputFormat;
+use crate::measure::scheduler::max_cpus;
+use crate::measure::statistics::OutlierDetection;
 use crate::pattern::PatternSource;
 use crate::range::Range;
 use crate::text::TextSource;
 
+/// How often each algorithm is executed for each pattern.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Executions {
+    /// A fixed number of executions.
+    Fixed(usize),
+    /// Executions are repeated until the confidence interval of the mean
+    /// runtime is narrow enough or the time limit is reached.
+    Adaptive,
+}
+
+/// The parameters given on the command line.
+///
+/// They are serialized into the metadata of a measurement, except for the
+/// internal parameters of child processes.
+#[derive(Serialize)]
 pub struct CLIParams {
     pub algorithms: Vec<String>,
 
     pub no_header: bool,
-
-    pub executions: usize,
-    pub seed: Option<u64>,
+    pub aggregate: bool,
+    pub output_files: Vec<String>,
+    pub summary_files: Vec<String>,
+    pub metadata_file: Option<String>,
+    pub checkpoint_file: Option<String>,
+    pub resume: bool,
+
+    pub executions: Executions,
+    pub warmup_executions: Option<usize>,
+    pub confidence_interval_width: f64,
+    pub cell_time_limit: f64,
+    pub outlier_detection: Option<OutlierDetection>,
+    pub exclude_outliers: bool,
+    pub track_memory: bool,
+    pub isolate: bool,
+    pub execution_timeout: Option<f64>,
+    pub cell_timeout: Option<f64>,
+    pub time_budget: Option<f64>,
+    pub algorithm_budget: Option<f64>,
+    pub pinned_cpu: Option<usize>,
+    pub nice: Option<i32>,
+    pub shuffle: bool,
+    pub threads: usize,
+    pub progress: bool,
+    pub query_mode: Option<QueryMode>,
+    pub cache_mode: Option<CacheMode>,
+    pub output_format: Option<OutputFormat>,
+    pub baseline: Option<String>,
+    pub chart_directory: Option<String>,
+    pub chart_inputs: Vec<String>,
+    pub chart_log_axes: Option<LogAxes>,
+    #[serde(skip)]
+    pub isolated_cell: Option<IsolatedCell>,
+    /// The seed given by `--seed` or drawn randomly if it hasn't been given.
+    pub seed: u64,
     pub alphabet_size: u8,
 
     pub pattern_source: PatternSource,
@@ -27,8 +82,20 @@ impl CLIParams {
     /// returns a new `CLIParams` object.
     pub fn new() -> Self {
         let clap_yaml = load_yaml!("cli.yml");
-        let matches = App::from_yaml(clap_yaml).get_matches();
 
+        Self::from_matches(App::from_yaml(clap_yaml).get_matches())
+    }
+
+    /// Parses the given CLI arguments, the first one being the name of the
+    /// program, and returns a new `CLIParams` object.
+    #[cfg(test)]
+    pub fn from_args(args: &[&str]) -> Self {
+        let clap_yaml = load_yaml!("cli.yml");
+
+        Self::from_matches(App::from_yaml(clap_yaml).get_matches_from(args))
+    }
+
+    fn from_matches(matches: ArgMatches) -> Self {
         // === String value parameters ===
         // For algorithms, unwrap is safe as it is a required parameter
         // whose existance is checked by the CLI argument parser.
@@ -42,43 +109,179 @@ impl CLIParams {
             .unwrap_or("sais")
             .to_string();
 
+        let output_files: Vec<String> = matches
+            .values_of("output_files")
+            .map_or(Vec::new(), |x| x.map(|x| x.to_string()).collect());
+        let summary_files: Vec<String> = matches
+            .values_of("summary_files")
+            .map_or(Vec::new(), |x| x.map(|x| x.to_string()).collect());
+        let metadata_file = matches.value_of("metadata_file").map(|x| x.to_string());
+        let checkpoint_file = matches.value_of("checkpoint_file").map(|x| x.to_string());
+        let outlier_detection: Option<OutlierDetection> = matches
+            .value_of("outlier_detection")
+            .unwrap_or("tukey")
+            .parse()
+            .ok();
+        let query_mode: Option<QueryMode> =
+            matches.value_of("query_mode").unwrap_or("all").parse().ok();
+        let cache_mode: Option<CacheMode> = matches
+            .value_of("cache_mode")
+            .unwrap_or("none")
+            .parse()
+            .ok();
+        let baseline = matches.value_of("baseline").map(|x| x.to_string());
+        let chart_directory = matches.value_of("chart_directory").map(|x| x.to_string());
+        let chart_inputs: Vec<String> = matches
+            .values_of("chart_inputs")
+            .map_or(Vec::new(), |x| x.map(|x| x.to_string()).collect());
+        let chart_log_axes: Option<LogAxes> = matches
+            .value_of("chart_log_axes")
+            .unwrap_or("none")
+            .parse()
+            .ok();
+        let output_format: Option<OutputFormat> = matches
+            .value_of("output_format")
+            .unwrap_or("csv")
+            .parse()
+            .ok();
+
         // === Bool value parameters ===
         let no_header: bool = matches.is_present("no_header");
+        let aggregate: bool = matches.is_present("aggregate");
+        let exclude_outliers: bool = matches.is_present("exclude_outliers");
+        let track_memory: bool = matches.is_present("track_memory");
+        let resume: bool = matches.is_present("resume");
+        let shuffle: bool = matches.is_present("shuffle");
+        let progress: bool = matches.is_present("progress");
+
+        // === Internal parameters ===
+        // Clap makes sure that exactly four values are given
+        let isolated_cell = matches.values_of("isolated_cell").map(|values| {
+            let values: Vec<&str> = values.collect();
+
+            IsolatedCell {
+                algorithm: values[0].to_string(),
+                pattern_index: values[1].parse().ok(),
+                text_file: values[2].into(),
+                patterns_file: values[3].into(),
+            }
+        });
 
         // === Number value parameters ===
-        let executions: usize = matches
-            .value_of("executions")
-            .unwrap_or("1") // 1 so that if parameter is not given, the default of one execution is used
-            .parse()
-            .unwrap_or(0); // 0 so that if invalid parameter is given, validation fails
-        let seed: Option<u64> = matches
-            .value_of("seed")
-            .unwrap_or("-1") // -1 so that parse fails if the argument is not set, resulting in seed being None
+        let executions = match matches.value_of("executions") {
+            Some("auto") => Executions::Adaptive,
+            executions => Executions::Fixed(
+                executions
+                    .unwrap_or("1") // 1 so that if parameter is not given, the default of one execution is used
+                    .parse()
+                    .unwrap_or(0), // 0 so that if invalid parameter is given, validation fails
+            ),
+        };
+        let warmup_executions: Option<usize> = matches
+            .value_of("warmup_executions")
+            .unwrap_or("0")
             .parse()
             .ok();
+        let confidence_interval_width: f64 = matches
+            .value_of("confidence_interval_width")
+            .unwrap_or("0.05")
+            .parse()
+            .unwrap_or(0f64);
+        let cell_time_limit: f64 = matches
+            .value_of("cell_time_limit")
+            .unwrap_or("10")
+            .parse()
+            .unwrap_or(0f64);
+        // Without a seed, one is drawn randomly, so that the run can still be
+        // reproduced using the seed recorded in its metadata. It is kept below
+        // 2^53, as many JSON parsers read numbers as doubles.
+        let seed: u64 = matches
+            .value_of("seed")
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_else(|| rand::random::<u64>() >> 11);
         let alphabet_size: u8 = matches
             .value_of("alphabet_size")
             .unwrap_or("254")
             .parse()
             .unwrap_or(0);
+        let threads: usize = matches
+            .value_of("threads")
+            .unwrap_or("1")
+            .parse()
+            .unwrap_or(0); // 0 so that if invalid parameter is given, validation fails
         let occ_block_size = matches
             .value_of("occ_block_size")
             .unwrap_or("1")
             .parse()
             .unwrap_or(0);
+        // 0 so that if invalid parameter is given, validation fails
+        let execution_timeout: Option<f64> = matches
+            .value_of("execution_timeout")
+            .map(|x| x.parse().unwrap_or(0f64));
+        let cell_timeout: Option<f64> = matches
+            .value_of("cell_timeout")
+            .map(|x| x.parse().unwrap_or(0f64));
+        let time_budget: Option<f64> = matches
+            .value_of("time_budget")
+            .map(|x| x.parse().unwrap_or(0f64));
+        let algorithm_budget: Option<f64> = matches
+            .value_of("algorithm_budget")
+            .map(|x| x.parse().unwrap_or(0f64));
+        // Values out of range so that if invalid parameter is given,
+        // validation fails
+        let pinned_cpu: Option<usize> = matches
+            .value_of("pinned_cpu")
+            .map(|x| x.parse().unwrap_or(usize::MAX));
+        let nice: Option<i32> = matches
+            .value_of("nice")
+            .map(|x| x.parse().unwrap_or(i32::MAX));
         let maximum_error = matches
             .value_of("maximum_error")
             .unwrap_or("0")
             .parse()
             .ok();
 
+        // Measurements can only be aborted if they run in a child process
+        let isolate: bool =
+            matches.is_present("isolate") || execution_timeout.is_some() || cell_timeout.is_some();
+
         // Return new CLIParams object
         Self {
             algorithms,
 
             no_header,
+            aggregate,
+            output_files,
+            summary_files,
+            metadata_file,
+            checkpoint_file,
+            resume,
 
             executions,
+            warmup_executions,
+            confidence_interval_width,
+            cell_time_limit,
+            outlier_detection,
+            exclude_outliers,
+            track_memory,
+            isolate,
+            execution_timeout,
+            cell_timeout,
+            time_budget,
+            algorithm_budget,
+            pinned_cpu,
+            nice,
+            shuffle,
+            threads,
+            progress,
+            query_mode,
+            cache_mode,
+            output_format,
+            baseline,
+            chart_directory,
+            chart_inputs,
+            chart_log_axes,
+            isolated_cell,
             seed,
             alphabet_size,
 
@@ -97,12 +300,12 @@ impl CLIParams {
 
         // String value parameters
         if match_algorithms(&self.algorithms).is_empty() {
-            println!("Unknown algorithm given.\n");
+            eprintln!("Unknown algorithm given.\n");
             valid = false;
         }
 
         if self.algorithms.contains(&String::from("all")) && self.algorithms.len() != 1 {
-            println!("You cannot specify multiple algorithms if you specify \"all\".");
+            eprintln!("You cannot specify multiple algorithms if you specify \"all\".");
             valid = false;
         }
 
@@ -112,7 +315,7 @@ impl CLIParams {
             .any(|x| x.starts_with("sa-") || x.starts_with("bwt-"))
             && !["naive", "sais"].contains(&self.suffix_array_algorithm.as_str())
         {
-            println!(
+            eprintln!(
                 "You have to specify a valid algorithm for suffix array generation. \
                 Options are: naive, sais.\nYou could also omit the parameter to use the \
                 default algorithm `sais`.\n"
@@ -120,18 +323,168 @@ impl CLIParams {
             valid = false;
         }
 
+        if self.outlier_detection.is_none() {
+            eprintln!(
+                "You have to specify a valid outlier detection method. Options are: \
+                none, tukey, mad.\nYou could also omit the parameter to use the \
+                default method `tukey`.\n"
+            );
+            valid = false;
+        }
+
+        if self.track_memory && !cfg!(feature = "memory") {
+            eprintln!(
+                "The --memory argument requires aas-benchmark to be built with the \
+                `memory` feature.\n"
+            );
+            valid = false;
+        }
+
+        if self.resume && self.checkpoint_file.is_none() {
+            eprintln!("The --resume argument requires a checkpoint file given by --checkpoint.\n");
+            valid = false;
+        }
+
+        if self.checkpoint_file.is_some() && !self.resumable_output() {
+            eprintln!(
+                "The --checkpoint argument requires a single results file in CSV or JSON \
+                lines format given by --output, the CSV header can't be omitted.\n"
+            );
+            valid = false;
+        }
+
+        if self.query_mode.is_none() {
+            eprintln!(
+                "You have to specify a valid query mode. Options are: count, \
+                first, exists, all.\nYou could also omit the parameter to use the \
+                default mode `all`.\n"
+            );
+            valid = false;
+        }
+
+        if self.cache_mode.is_none() {
+            eprintln!(
+                "You have to specify a valid cache mode. Options are: cold, warm, \
+                none.\nYou could also omit the parameter to use the default mode \
+                `none`.\n"
+            );
+            valid = false;
+        }
+
+        if self.output_format.is_none() {
+            eprintln!(
+                "You have to specify a valid output format. Options are: csv, json, \
+                jsonl, table.\nYou could also omit the parameter to use the default format \
+                `csv`.\n"
+            );
+            valid = false;
+        }
+
+        if let Some(baseline) = &self.baseline {
+            if !match_algorithms(&self.algorithms)
+                .iter()
+                .any(|(algorithm, _)| algorithm == baseline)
+            {
+                eprintln!("The --baseline argument needs to be one of the measured algorithms.\n");
+                valid = false;
+            }
+        }
+
+        if self.chart_log_axes.is_none() {
+            eprintln!(
+                "You have to specify valid log axes for the charts. Options are: none, \
+                x, y, xy.\nYou could also omit the parameter to use the default `none`.\n"
+            );
+            valid = false;
+        }
+
+        if !self.chart_inputs.is_empty() && self.chart_directory.is_none() {
+            eprintln!("The --chartinput argument requires a chart directory given by --charts.\n");
+            valid = false;
+        }
+
         // Number value parameters
         if self.alphabet_size < 1 || self.alphabet_size > 254 {
-            println!("The -a argument needs to be a positive integer between 1 and 254.\n");
+            eprintln!("The -a argument needs to be a positive integer between 1 and 254.\n");
+            valid = false;
+        }
+        if self.executions == Executions::Fixed(0) {
+            eprintln!("The -n argument needs to be a positive integer greater than 0 or `auto`.\n");
             valid = false;
         }
-        if self.executions == 0 {
-            println!("The -n argument needs to be a positive integer greater than 0.\n");
+        if self.warmup_executions.is_none() {
+            eprintln!(
+                "The --warmup argument needs to be a positive integer.\nYou could \
+                also omit the parameter to use the default value of 0.\n"
+            );
             valid = false;
         }
+        if self.executions == Executions::Adaptive {
+            if !self.confidence_interval_width.is_finite() || self.confidence_interval_width <= 0f64
+            {
+                eprintln!("The --ciwidth argument needs to be a positive number.\n");
+                valid = false;
+            }
+            if !self.cell_time_limit.is_finite() || self.cell_time_limit <= 0f64 {
+                eprintln!("The --celltimelimit argument needs to be a positive number.\n");
+                valid = false;
+            }
+        }
+
+        if let Some(execution_timeout) = self.execution_timeout {
+            if !execution_timeout.is_finite() || execution_timeout <= 0f64 {
+                eprintln!("The --timeout argument needs to be a positive number.\n");
+                valid = false;
+            }
+        }
+        if let Some(cell_timeout) = self.cell_timeout {
+            if !cell_timeout.is_finite() || cell_timeout <= 0f64 {
+                eprintln!("The --celltimeout argument needs to be a positive number.\n");
+                valid = false;
+            }
+        }
+        if let Some(time_budget) = self.time_budget {
+            if !time_budget.is_finite() || time_budget <= 0f64 {
+                eprintln!("The --budget argument needs to be a positive number.\n");
+                valid = false;
+            }
+        }
+        if let Some(algorithm_budget) = self.algorithm_budget {
+            if !algorithm_budget.is_finite() || algorithm_budget <= 0f64 {
+                eprintln!("The --algorithmbudget argument needs to be a positive number.\n");
+                valid = false;
+            }
+        }
+
+        if !cfg!(target_os = "linux") && (self.pinned_cpu.is_some() || self.nice.is_some()) {
+            eprintln!("The --pin and --nice arguments are only supported on Linux.\n");
+            valid = false;
+        } else if let Some(pinned_cpu) = self.pinned_cpu {
+            if pinned_cpu >= max_cpus() {
+                eprintln!(
+                    "The --pin argument needs to be a positive integer less than {}.\n",
+                    max_cpus()
+                );
+                valid = false;
+            }
+        }
+        if self.threads == 0 {
+            eprintln!("The --threads argument needs to be a positive integer greater than 0.\n");
+            valid = false;
+        }
+        if self.threads > 1 && self.pinned_cpu.is_some() {
+            eprintln!("The --pin argument can't be used with more than one thread.\n");
+            valid = false;
+        }
+        if let Some(nice) = self.nice {
+            if !(-20..=19).contains(&nice) {
+                eprintln!("The --nice argument needs to be an integer between -20 and 19.\n");
+                valid = false;
+            }
+        }
 
         if self.algorithms.contains(&String::from("bwt-match-k")) && self.occ_block_size == 0 {
-            println!(
+            eprintln!(
                 "You have to enter a valid block size for the Occ array when \
                 using the `bwt-match-k` algorithm or omit the parameter to use \
                 the default block size of 1."
@@ -140,7 +493,7 @@ impl CLIParams {
         }
 
         if self.maximum_error.is_none() {
-            println!(
+            eprintln!(
                 "The --maxerror argument needs to be a positive integer.\nYou could \
                 also omit the parameter to use the default value of 0.\n"
             );
@@ -149,18 +502,34 @@ impl CLIParams {
 
         // Other type paramters
         if let PatternSource::Error(err) = self.pattern_source {
-            println!("Error while parsing pattern source: {}", err);
+            eprintln!("Error while parsing pattern source: {}", err);
             valid = false;
         }
 
         if let TextSource::Error(err) = self.text_source {
-            println!("Error while parsing text source: {}", err);
+            eprintln!("Error while parsing text source: {}", err);
             valid = false;
         }
 
         valid
     }
 
+    /// Returns whether the results are written to a single file which can be
+    /// continued when resuming the measurement, i. e. a CSV file with header
+    /// or a JSON lines file.
+    fn resumable_output(&self) -> bool {
+        match self.output_files.as_slice() {
+            [file_name] if file_name != "-" => {
+                match OutputFormat::from_extension(file_name).or(self.output_format) {
+                    Some(OutputFormat::Csv) => !self.no_header,
+                    Some(OutputFormat::Jsonl) => true,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
     fn set_pattern_source(matches: &ArgMatches) -> PatternSource {
         let pattern_from_argument: bool = matches.is_present("pattern_from_argument");
         let pattern_from_file: bool = matches.is_present("pattern_from_file");
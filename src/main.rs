@@ -21,13 +21,22 @@ mod range;
 mod text;
 
 use std::error::Error;
+use std::process;
 
 use cli::CLIParams;
 use match_algorithm::match_algorithms;
+use measure::isolation::run_isolated_cell;
 use measure::measurement::Measurement;
+#[cfg(feature = "memory")]
+use measure::memory::TrackingAllocator;
 use pattern::generate_patterns;
 use text::generate_text;
 
+/// Tracks heap allocations if memory tracking is enabled using `--memory`.
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
 #[cfg(not(tarpaulin_include))]
 fn main() -> Result<(), Box<dyn Error>> {
     // Get CLI parameters using Clap
@@ -36,6 +45,12 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Only continue if all given parameters are valid, all unwraps are safe
     // here because of the checks done in cli_params.valid()
     if cli_params.valid() {
+        // Child processes of isolated measurements only measure a single
+        // cell and report back to their parent
+        if let Some(isolated_cell) = &cli_params.isolated_cell {
+            return run_isolated_cell(isolated_cell, &cli_params);
+        }
+
         let text = generate_text(&cli_params);
 
         if let Ok(text) = &text {
@@ -44,15 +59,39 @@ fn main() -> Result<(), Box<dyn Error>> {
             if let Ok(patterns) = patterns {
                 let algorithms = match_algorithms(&cli_params.algorithms);
 
-                Measurement::new(algorithms, text.to_vec(), patterns, cli_params)
-                    .run_measurement()
-                    .print_csv()
-                    .expect("Internal error");
+                let mut measurement =
+                    Measurement::new(algorithms, text.to_vec(), patterns, cli_params);
+
+                // The metadata is written first, so that it is kept even if
+                // the measurement is aborted
+                if let Err(err) = measurement.write_metadata() {
+                    eprintln!("Error while writing metadata: {}", err);
+                }
+
+                match measurement.run_measurement() {
+                    Ok(measurement) => {
+                        if let Err(err) = measurement.write_summary() {
+                            eprintln!("Error while writing summary: {}", err);
+                        }
+
+                        if let Err(err) = measurement.write_charts() {
+                            eprintln!("Error while writing charts: {}", err);
+                        }
+                    }
+                    Err(err) => {
+                        eprintln!("Error while running measurement: {}", err);
+
+                        // Scripts mustn't mistake an aborted measurement,
+                        // e. g. because of an existing checkpoint file, for
+                        // a completed one
+                        process::exit(1);
+                    }
+                }
             } else if let Err(err) = patterns {
-                println!("Error while generating pattern source: {}", err);
+                eprintln!("Error while generating pattern source: {}", err);
             }
         } else if let Err(err) = text {
-            println!("Error while generating text source: {}", err);
+            eprintln!("Error while generating text source: {}", err);
         }
     }
 
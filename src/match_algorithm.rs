@@ -1,49 +1,52 @@
 use std::collections::HashMap;
+use std::ops::Range;
 
 use crate::algorithms::approximative::error_tolerant_shift_and::error_tolerant_shift_and;
 use crate::algorithms::approximative::ukkonen::ukkonen;
 use crate::algorithms::full_text_indices::sais::fast;
 use crate::algorithms::full_text_indices::suffix_array::slow;
 use crate::algorithms::full_text_indices::suffix_array_algorithms::{
-    match_pattern, match_pattern_bwt,
+    match_pattern_bwt_interval, match_pattern_interval,
 };
 use crate::algorithms::multiple_patterns::aho_corasick::aho_corasick;
 use crate::algorithms::multiple_patterns::naive::naive_multiple;
-use crate::algorithms::single_pattern::blim::blim;
-use crate::algorithms::single_pattern::bndm::bndm;
-use crate::algorithms::single_pattern::bom::bom;
+use crate::algorithms::occurrences::{Occurrences, QueryMode};
+use crate::algorithms::single_pattern::blim::blim_prepare;
+use crate::algorithms::single_pattern::bndm::bndm_prepare;
+use crate::algorithms::single_pattern::bom::bom_prepare;
 use crate::algorithms::single_pattern::boyer_moore::{
-    weak_boyer_moore_all, weak_memorizing_boyer_moore_all, weak_turbo_boyer_moore_all,
+    weak_boyer_moore_prepare, weak_memorizing_boyer_moore_prepare, weak_turbo_boyer_moore_prepare,
 };
-use crate::algorithms::single_pattern::double_window::double_window;
-use crate::algorithms::single_pattern::double_window::double_window_alt;
-use crate::algorithms::single_pattern::horspool::horspool_all;
-use crate::algorithms::single_pattern::kmp::{kmp_all, kmp_classic_all};
-use crate::algorithms::single_pattern::naive::naive_all;
-use crate::algorithms::single_pattern::shift_and::shift_and;
+use crate::algorithms::single_pattern::double_window::double_window_alt_prepare;
+use crate::algorithms::single_pattern::double_window::double_window_prepare;
+use crate::algorithms::single_pattern::horspool::horspool_prepare;
+use crate::algorithms::single_pattern::kmp::{kmp_classic_prepare, kmp_prepare};
+use crate::algorithms::single_pattern::naive::naive_prepare;
+use crate::algorithms::single_pattern::shift_and::shift_and_prepare;
+use crate::algorithms::single_pattern::PreparedSearcher;
 
 lazy_static! {
     /// List of existing algorithms and their internal names
     static ref ALGORITHMS: HashMap<&'static str, TypedAlgorithm> = hashmap! {
-        "bndm" => TypedAlgorithm::SinglePatternAlgorithm(bndm),
-        "horspool" => TypedAlgorithm::SinglePatternAlgorithm(horspool_all),
-        "naive" => TypedAlgorithm::SinglePatternAlgorithm(naive_all),
-        "wbm" => TypedAlgorithm::SinglePatternAlgorithm(weak_boyer_moore_all),
-        "wmbm" => TypedAlgorithm::SinglePatternAlgorithm(weak_memorizing_boyer_moore_all),
-        "wtbm" => TypedAlgorithm::SinglePatternAlgorithm(weak_turbo_boyer_moore_all),
-        "kmp" => TypedAlgorithm::SinglePatternAlgorithm(kmp_all),
-        "kmp-classic" => TypedAlgorithm::SinglePatternAlgorithm(kmp_classic_all),
-        "shift-and" => TypedAlgorithm::SinglePatternAlgorithm(shift_and),
-        "sa-match" => TypedAlgorithm::SuffixArrayAlgorithm(match_pattern),
-        "bwt-match" => TypedAlgorithm::BWTAlgorithm(match_pattern_bwt),
+        "bndm" => TypedAlgorithm::SinglePatternAlgorithm(bndm_prepare),
+        "horspool" => TypedAlgorithm::SinglePatternAlgorithm(horspool_prepare),
+        "naive" => TypedAlgorithm::SinglePatternAlgorithm(naive_prepare),
+        "wbm" => TypedAlgorithm::SinglePatternAlgorithm(weak_boyer_moore_prepare),
+        "wmbm" => TypedAlgorithm::SinglePatternAlgorithm(weak_memorizing_boyer_moore_prepare),
+        "wtbm" => TypedAlgorithm::SinglePatternAlgorithm(weak_turbo_boyer_moore_prepare),
+        "kmp" => TypedAlgorithm::SinglePatternAlgorithm(kmp_prepare),
+        "kmp-classic" => TypedAlgorithm::SinglePatternAlgorithm(kmp_classic_prepare),
+        "shift-and" => TypedAlgorithm::SinglePatternAlgorithm(shift_and_prepare),
+        "sa-match" => TypedAlgorithm::SuffixArrayAlgorithm(match_pattern_interval),
+        "bwt-match" => TypedAlgorithm::BWTAlgorithm(match_pattern_bwt_interval),
         "ukkonen" => TypedAlgorithm::ApproximativeAlgorithm(ukkonen),
         "et-shift-and" => TypedAlgorithm::ApproximativeAlgorithm(error_tolerant_shift_and),
         "mp-naive" => TypedAlgorithm::MultiplePatternAlgorithm(naive_multiple),
         "aho-corasick" => TypedAlgorithm::MultiplePatternAlgorithm(aho_corasick),
-        "bom" => TypedAlgorithm::SinglePatternAlgorithm(bom),
-        "dw" => TypedAlgorithm::SinglePatternAlgorithm(double_window),
-        "blim" => TypedAlgorithm::SinglePatternAlgorithm(blim),
-        "dw-alt" => TypedAlgorithm::SinglePatternAlgorithm(double_window_alt),
+        "bom" => TypedAlgorithm::SinglePatternAlgorithm(bom_prepare),
+        "dw" => TypedAlgorithm::SinglePatternAlgorithm(double_window_prepare),
+        "blim" => TypedAlgorithm::SinglePatternAlgorithm(blim_prepare),
+        "dw-alt" => TypedAlgorithm::SinglePatternAlgorithm(double_window_alt_prepare),
     };
 
     /// List of suffix array generation algorithms and their internal names
@@ -73,24 +76,31 @@ lazy_static! {
         "dw" => "Double Window",
         "blim" => "BLIM",
         "dw-alt" => "Alt. Double Window",
+        "sa-naive" => "Naive Suffix Array Construction",
+        "sa-sais" => "SAIS Suffix Array Construction",
+        "bwt-index" => "BWT Index Construction",
     };
 }
 
-/// A single pattern algorithm.
-pub type SinglePatternAlgorithm = fn(&[u8], &[u8]) -> Vec<usize>;
+/// A single pattern algorithm, given as a function preprocessing a pattern
+/// and returning a searcher for it.
+pub type SinglePatternAlgorithm = fn(&[u8]) -> Box<dyn PreparedSearcher>;
 
-/// A multiple pattern algorithm.
-pub type MultiplePatternAlgorithm = fn(&[Vec<u8>], &[u8]) -> Vec<Vec<usize>>;
+/// A multiple pattern algorithm, returning the occurrences of each pattern
+/// collected according to the given query mode.
+pub type MultiplePatternAlgorithm = fn(&[Vec<u8>], &[u8], QueryMode) -> Vec<Occurrences>;
 
-/// A suffix array algorithm tuple, containing the algorithm itself and
-/// the suffix array generation function to be used.
-pub type SuffixArrayAlgorithm = fn(&[usize], &[u8], &[u8]) -> Vec<usize>;
+/// A suffix array algorithm, returning the interval of the suffix array
+/// containing the occurrences of a pattern.
+pub type SuffixArrayAlgorithm = fn(&[usize], &[u8], &[u8]) -> Range<usize>;
 
-/// A BWT algorithm.
-pub type BWTAlgorithm = fn(&[usize], &[usize], &[usize], &[u8]) -> Vec<usize>;
+/// A BWT algorithm, returning the interval of the suffix array containing
+/// the occurrences of a pattern using the Occ and less vectors of the BWT.
+pub type BWTAlgorithm = fn(&[usize], &[usize], &[u8]) -> Range<usize>;
 
-/// An approximative algorithm.
-pub type ApproximativeAlgorithm = fn(&[u8], &[u8], usize) -> Vec<(usize, usize)>;
+/// An approximative algorithm, reporting each occurrence together with its
+/// error.
+pub type ApproximativeAlgorithm = fn(&[u8], &[u8], usize, &mut Occurrences<(usize, usize)>);
 
 /// A suffix array generation algorithm.
 pub type SuffixArrayGenAlgorithm = fn(&[u8]) -> Vec<usize>;
@@ -130,9 +140,14 @@ pub fn match_algorithms(algorithm_names: &[String]) -> Vec<(String, TypedAlgorit
     let mut algorithms = Vec::new();
 
     for algorithm_name in algorithm_names.iter() {
-        // Special case for adding all algorithms
+        // Special case for adding all algorithms, sorted by their names as
+        // the order of the map differs between processes, but has to be the
+        // same when resuming a measurement
         if algorithm_name == "all" {
-            for (algorithm_name, algorithm) in ALGORITHMS.iter() {
+            let mut all_algorithms: Vec<_> = ALGORITHMS.iter().collect();
+            all_algorithms.sort_unstable_by_key(|x| x.0);
+
+            for (algorithm_name, algorithm) in all_algorithms {
                 algorithms.push((algorithm_name.to_string(), *algorithm));
             }
         } else if let Some(algorithm) = match_algorithm(algorithm_name) {
@@ -172,3 +187,14 @@ pub fn algorithm_name(algorithm: &str) -> &str {
         "Unknown Algorithm"
     }
 }
+
+/// Returns the CLI parameter name of an algorithm matching the given pretty
+/// formatted name, the inverse of `algorithm_name`.
+///
+/// It returns `None` if there is no algorithm with the given name.
+pub fn algorithm_argument(name: &str) -> Option<&'static str> {
+    ALGORITHM_NAMES
+        .iter()
+        .find(|(_, x)| **x == name)
+        .map(|(argument, _)| *argument)
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::cli::CLIParams;
+
+/// The time a measurement may take in total and for each algorithm.
+///
+/// Once an algorithm has used up its budget, its remaining cells are
+/// skipped, cells that have already started are measured completely. Once
+/// the total budget has been used up, all remaining cells are skipped and
+/// the cells that have already started are stopped at the `deadline`, so
+/// that the measurement ends with the results taken so far.
+pub struct Budget {
+    start: Instant,
+    total: Option<Duration>,
+    per_algorithm: Option<Duration>,
+    /// The time spent measuring each algorithm given by its index.
+    spent: Mutex<HashMap<usize, Duration>>,
+}
+
+impl Budget {
+    /// Starts the budgets given by the `--budget` and `--algorithmbudget`
+    /// arguments.
+    pub fn new(cli_params: &CLIParams) -> Self {
+        Self::with_limits(
+            cli_params.time_budget.map(Duration::from_secs_f64),
+            cli_params.algorithm_budget.map(Duration::from_secs_f64),
+        )
+    }
+
+    fn with_limits(total: Option<Duration>, per_algorithm: Option<Duration>) -> Self {
+        Self {
+            start: Instant::now(),
+            total,
+            per_algorithm,
+            spent: Mutex::default(),
+        }
+    }
+
+    /// Returns the time the total budget is used up at, `None` if there is
+    /// no total budget.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.total.map(|x| self.start + x)
+    }
+
+    /// Adds the time spent measuring the algorithm with the given index.
+    pub fn charge(&self, algorithm_index: usize, duration: Duration) {
+        // The mutex can only be poisoned if a worker has panicked, in which
+        // case the panic is propagated anyway, so unwrapping its lock is safe
+        // here
+        *self
+            .spent
+            .lock()
+            .unwrap()
+            .entry(algorithm_index)
+            .or_default() += duration;
+    }
+
+    /// Returns whether the algorithm with the given index mustn't be
+    /// measured any further, because either its own budget or the total
+    /// budget has been used up.
+    pub fn exhausted(&self, algorithm_index: usize) -> bool {
+        if self.total.is_some_and(|x| self.start.elapsed() >= x) {
+            return true;
+        }
+
+        self.per_algorithm.is_some_and(|x| {
+            self.spent
+                .lock()
+                .unwrap()
+                .get(&algorithm_index)
+                .is_some_and(|&spent| spent >= x)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_algorithm_budget() {
+        let budget = Budget::with_limits(None, Some(Duration::from_secs(2)));
+
+        assert!(!budget.exhausted(0));
+
+        budget.charge(0, Duration::from_secs(1));
+        budget.charge(1, Duration::from_secs(3));
+        assert!(!budget.exhausted(0));
+        assert!(budget.exhausted(1));
+
+        budget.charge(0, Duration::from_secs(1));
+        assert!(budget.exhausted(0));
+        assert!(!budget.exhausted(2));
+    }
+
+    #[test]
+    fn test_deadline() {
+        let budget = Budget::with_limits(Some(Duration::from_secs(60)), None);
+
+        assert_eq!(
+            budget.deadline(),
+            Some(budget.start + Duration::from_secs(60))
+        );
+        assert_eq!(
+            Budget::with_limits(None, Some(Duration::from_secs(60))).deadline(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_total_budget() {
+        assert!(Budget::with_limits(Some(Duration::from_secs(0)), None).exhausted(0));
+        assert!(!Budget::with_limits(Some(Duration::from_secs(3600)), None).exhausted(0));
+        assert!(!Budget::with_limits(None, None).exhausted(0));
+    }
+}
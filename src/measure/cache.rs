@@ -0,0 +1,125 @@
+use std::cell::RefCell;
+use std::fs;
+use std::hint::black_box;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// The size of a cache line in bytes, i. e. the stride in which the
+/// eviction buffer is touched.
+const CACHE_LINE_SIZE: usize = 64;
+
+/// The size of the last-level cache in bytes assumed if it can't be read
+/// from the system.
+const DEFAULT_LAST_LEVEL_CACHE_SIZE: usize = 32 * 1024 * 1024;
+
+/// How much larger than the last-level cache the eviction buffer is, so that
+/// the cache is fully replaced even if its replacement policy isn't exactly
+/// least recently used.
+const EVICTION_BUFFER_FACTOR: usize = 2;
+
+/// The state of the CPU caches when a phase of an algorithm is measured.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheMode {
+    /// The caches are evicted before each timed phase, so that the text and
+    /// the tables have to be loaded from main memory.
+    Cold,
+    /// The phase is executed once before it is timed, so that the text and
+    /// the tables it reads are already cached.
+    Warm,
+    /// The caches are left in whatever state the previous execution left
+    /// them in.
+    None,
+}
+
+impl FromStr for CacheMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cold" => Ok(Self::Cold),
+            "warm" => Ok(Self::Warm),
+            "none" => Ok(Self::None),
+            _ => Err(format!("unknown cache mode `{}`", s)),
+        }
+    }
+}
+
+thread_local! {
+    // Allocated on first use, so that only threads measuring with cold
+    // caches hold a buffer
+    static EVICTION_BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Evicts the text and the tables of the algorithms from the CPU caches by
+/// streaming through a buffer larger than the last-level cache.
+///
+/// Every cache line of the buffer is written to, so that the cached lines
+/// are replaced by lines of the buffer on all cache levels.
+pub fn evict_caches() {
+    EVICTION_BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+
+        if buffer.is_empty() {
+            buffer.resize(EVICTION_BUFFER_FACTOR * last_level_cache_size(), 0);
+        }
+
+        for byte in buffer.iter_mut().step_by(CACHE_LINE_SIZE) {
+            *byte = byte.wrapping_add(1);
+        }
+
+        black_box(&mut *buffer);
+    });
+}
+
+/// Returns the size of the largest CPU cache in bytes as reported by the
+/// kernel (Linux only).
+///
+/// If the size can't be read, e. g. on other operating systems, it returns
+/// a default size of 32 MiB.
+pub fn last_level_cache_size() -> usize {
+    fs::read_dir("/sys/devices/system/cpu/cpu0/cache")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| fs::read_to_string(entry.path().join("size")).ok())
+        .filter_map(|size| parse_cache_size(&size))
+        .max()
+        .unwrap_or(DEFAULT_LAST_LEVEL_CACHE_SIZE)
+}
+
+/// Parses a cache size as given by the kernel, e. g. `2048K`, into bytes.
+pub fn parse_cache_size(size: &str) -> Option<usize> {
+    let size = size.trim();
+
+    let (number, factor) = match size.chars().last()? {
+        'K' => (&size[..size.len() - 1], 1024),
+        'M' => (&size[..size.len() - 1], 1024 * 1024),
+        'G' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+
+    number.parse::<usize>().ok().map(|x| x * factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cache_size() {
+        assert_eq!(parse_cache_size("48K\n"), Some(48 * 1024));
+        assert_eq!(parse_cache_size("32M"), Some(32 * 1024 * 1024));
+        assert_eq!(parse_cache_size("512"), Some(512));
+        assert_eq!(parse_cache_size(""), None);
+        assert_eq!(parse_cache_size("abcK"), None);
+    }
+
+    #[test]
+    fn test_cache_mode_from_str() {
+        assert_eq!("cold".parse(), Ok(CacheMode::Cold));
+        assert_eq!("none".parse(), Ok(CacheMode::None));
+        assert!("hot".parse::<CacheMode>().is_err());
+    }
+}
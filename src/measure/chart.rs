@@ -0,0 +1,847 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::match_algorithm::{algorithm_argument, match_algorithm};
+use crate::measure::measurement_result::Status;
+use crate::measure::statistics::Summary;
+
+const WIDTH: f64 = 800f64;
+const HEIGHT: f64 = 500f64;
+const MARGIN_LEFT: f64 = 90f64;
+/// The space right of the plot, which holds the legend.
+const MARGIN_RIGHT: f64 = 220f64;
+const MARGIN_TOP: f64 = 50f64;
+const MARGIN_BOTTOM: f64 = 60f64;
+/// The space below the plot of bar charts, which holds the rotated labels.
+const MARGIN_BOTTOM_BARS: f64 = 160f64;
+/// The width reserved for each bar of a bar chart.
+const BAR_SLOT_WIDTH: f64 = 28f64;
+
+/// The colors of the series, repeated if there are more series.
+const COLORS: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf",
+];
+
+/// The axes of the line charts that are scaled logarithmically.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct LogAxes {
+    pub x: bool,
+    pub y: bool,
+}
+
+impl FromStr for LogAxes {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::default()),
+            "x" => Ok(Self { x: true, y: false }),
+            "y" => Ok(Self { x: false, y: true }),
+            "xy" => Ok(Self { x: true, y: true }),
+            _ => Err(format!("unknown log axes `{}`", s)),
+        }
+    }
+}
+
+/// The runtimes of a single execution of an algorithm, the data all charts
+/// are drawn from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChartSample {
+    /// The name of the algorithm as given in the `algorithm` column.
+    pub algorithm: String,
+    pub text_length: usize,
+    pub pattern_length: usize,
+    pub preparation_time_ns: f64,
+    pub time_ns: f64,
+}
+
+/// The columns of a raw CSV results file needed for the charts, all other
+/// columns are ignored.
+#[derive(Deserialize)]
+struct CSVSample {
+    algorithm: String,
+    text_length: usize,
+    pattern_length: usize,
+    status: Status,
+    prep_time_ns: Option<f64>,
+    time_ns: Option<f64>,
+    outlier: Option<bool>,
+}
+
+/// Returns whether an execution of the algorithm given by its CLI parameter
+/// name is charted, so that the samples of this run and of earlier runs are
+/// filtered alike.
+///
+/// Index constructions and failed measurements aren't charted, as their
+/// search times can't be compared, and neither are outliers if
+/// `exclude_outliers` is set.
+pub fn charted(algorithm: &str, status: Status, outlier: bool, exclude_outliers: bool) -> bool {
+    status == Status::Ok && match_algorithm(algorithm).is_some() && !(exclude_outliers && outlier)
+}
+
+/// Reads the samples from a CSV file of raw results written by an earlier
+/// run, e. g. with another text length, leaving out the executions that
+/// aren't `charted`.
+///
+/// It returns an error if the file can't be read or has been written
+/// without a header.
+pub fn read_samples(file_name: &str, exclude_outliers: bool) -> Result<Vec<ChartSample>, String> {
+    let mut reader =
+        csv::Reader::from_path(file_name).map_err(|error| format!("{}: {}", file_name, error))?;
+    let mut samples = Vec::new();
+
+    for record in reader.deserialize() {
+        let record: CSVSample = record.map_err(|error| format!("{}: {}", file_name, error))?;
+
+        // The results contain the display names of the algorithms
+        let algorithm = algorithm_argument(&record.algorithm);
+        let outlier = record.outlier.unwrap_or(false);
+
+        if !algorithm.is_some_and(|x| charted(x, record.status, outlier, exclude_outliers)) {
+            continue;
+        }
+
+        if let Some(time_ns) = record.time_ns {
+            samples.push(ChartSample {
+                algorithm: record.algorithm,
+                text_length: record.text_length,
+                pattern_length: record.pattern_length,
+                preparation_time_ns: record.prep_time_ns.unwrap_or(0f64),
+                time_ns,
+            });
+        }
+    }
+
+    Ok(samples)
+}
+
+/// Writes the charts of the given samples as SVG files to `directory`,
+/// creating it if it doesn't exist:
+///
+/// - `runtime_by_pattern_length.svg`: the search time by pattern length
+/// - `runtime_by_text_length.svg`: the search time by text length
+/// - `preparation_and_search.svg`: the preparation and search times stacked
+///   for each algorithm, text and pattern length
+///
+/// Each point and bar shows the median of all executions, its error bar
+/// reaches from the 5th to the 95th percentile. The `log_axes` apply to the
+/// line charts only, as stacked bars can't be compared on a log scale.
+pub fn write_charts(
+    directory: &str,
+    samples: &[ChartSample],
+    log_axes: LogAxes,
+) -> Result<(), String> {
+    let directory = Path::new(directory);
+    fs::create_dir_all(directory).map_err(|error| error.to_string())?;
+
+    let text_lengths: HashSet<usize> = samples.iter().map(|x| x.text_length).collect();
+    let pattern_lengths: HashSet<usize> = samples.iter().map(|x| x.pattern_length).collect();
+
+    // Series are only told apart by the other length if there are several
+    let by_pattern_length = group(samples, |sample| {
+        let name = if text_lengths.len() > 1 {
+            format!("{} (n = {})", sample.algorithm, sample.text_length)
+        } else {
+            sample.algorithm.clone()
+        };
+
+        (name, sample.pattern_length, sample.time_ns)
+    });
+    let by_text_length = group(samples, |sample| {
+        let name = if pattern_lengths.len() > 1 {
+            format!("{} (m = {})", sample.algorithm, sample.pattern_length)
+        } else {
+            sample.algorithm.clone()
+        };
+
+        (name, sample.text_length, sample.time_ns)
+    });
+
+    let charts = [
+        (
+            "runtime_by_pattern_length.svg",
+            line_chart(
+                "Search time by pattern length",
+                "Pattern length",
+                &by_pattern_length,
+                log_axes,
+            ),
+        ),
+        (
+            "runtime_by_text_length.svg",
+            line_chart(
+                "Search time by text length",
+                "Text length",
+                &by_text_length,
+                log_axes,
+            ),
+        ),
+        (
+            "preparation_and_search.svg",
+            stacked_bar_chart(&bars(samples, text_lengths.len() > 1)),
+        ),
+    ];
+
+    for (file_name, svg) in charts.iter() {
+        fs::write(directory.join(file_name), svg).map_err(|error| error.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// A line of a line chart, mapping each x value to the values measured at
+/// it.
+struct Series {
+    name: String,
+    values: BTreeMap<usize, Vec<f64>>,
+}
+
+/// Groups the samples into series using `key`, which returns the name of
+/// the series, the x value and the y value of a sample.
+///
+/// The series are ordered by the first sample belonging to them.
+fn group<F: Fn(&ChartSample) -> (String, usize, f64)>(
+    samples: &[ChartSample],
+    key: F,
+) -> Vec<Series> {
+    let mut series: Vec<Series> = Vec::new();
+
+    for sample in samples {
+        let (name, x, y) = key(sample);
+
+        let index = match series.iter().position(|x| x.name == name) {
+            Some(index) => index,
+            None => {
+                series.push(Series {
+                    name,
+                    values: BTreeMap::new(),
+                });
+
+                series.len() - 1
+            }
+        };
+
+        series[index].values.entry(x).or_default().push(y);
+    }
+
+    series
+}
+
+/// A bar of the stacked bar chart with the preparation and search times of
+/// all executions of an algorithm with one text and pattern length.
+struct Bar {
+    label: String,
+    preparation: Vec<f64>,
+    search: Vec<f64>,
+}
+
+/// Groups the samples into bars in the order of the first sample belonging
+/// to them, labeling them with the text length only if `text_lengths` is
+/// set.
+fn bars(samples: &[ChartSample], text_lengths: bool) -> Vec<Bar> {
+    let mut bars: Vec<Bar> = Vec::new();
+
+    for sample in samples {
+        let label = if text_lengths {
+            format!(
+                "{} (n = {}, m = {})",
+                sample.algorithm, sample.text_length, sample.pattern_length
+            )
+        } else {
+            format!("{} (m = {})", sample.algorithm, sample.pattern_length)
+        };
+
+        let index = match bars.iter().position(|x| x.label == label) {
+            Some(index) => index,
+            None => {
+                bars.push(Bar {
+                    label,
+                    preparation: Vec::new(),
+                    search: Vec::new(),
+                });
+
+                bars.len() - 1
+            }
+        };
+
+        bars[index].preparation.push(sample.preparation_time_ns);
+        bars[index].search.push(sample.time_ns);
+    }
+
+    bars
+}
+
+/// An axis mapping values to the fraction of the plot they are drawn at.
+struct Axis {
+    min: f64,
+    max: f64,
+    log: bool,
+}
+
+impl Axis {
+    /// Creates an axis covering all given values.
+    ///
+    /// Linear axes are extended to multiples of their tick step and start at
+    /// 0 if `from_zero` is set, log axes are extended to powers of ten and
+    /// ignore values that aren't positive.
+    fn new(values: &[f64], log: bool, from_zero: bool) -> Self {
+        let values: Vec<f64> = values
+            .iter()
+            .copied()
+            .filter(|x| x.is_finite() && (!log || *x > 0f64))
+            .collect();
+
+        let mut min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let mut max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        if values.is_empty() {
+            min = if log { 1f64 } else { 0f64 };
+            max = min;
+        }
+
+        if log {
+            min = 10f64.powf(min.log10().floor());
+            max = 10f64.powf(max.log10().ceil());
+
+            if max <= min {
+                max = min * 10f64;
+            }
+        } else {
+            if from_zero {
+                min = min.min(0f64);
+            }
+
+            if max <= min {
+                min -= 1f64;
+                max += 1f64;
+            }
+
+            let step = tick_step(max - min);
+            min = (min / step).floor() * step;
+            max = (max / step).ceil() * step;
+        }
+
+        Self { min, max, log }
+    }
+
+    /// Returns the fraction of the axis at which `value` is drawn, 0 at the
+    /// minimum and 1 at the maximum.
+    fn fraction(&self, value: f64) -> f64 {
+        if self.log {
+            (value.log10() - self.min.log10()) / (self.max.log10() - self.min.log10())
+        } else {
+            (value - self.min) / (self.max - self.min)
+        }
+    }
+
+    /// Returns whether `value` can be drawn on this axis.
+    fn contains(&self, value: f64) -> bool {
+        value.is_finite() && (!self.log || value > 0f64)
+    }
+
+    /// Returns the values at which ticks are drawn and their labels.
+    fn ticks(&self) -> Vec<(f64, String)> {
+        if self.log {
+            let first = self.min.log10().round() as i32;
+            let last = self.max.log10().round() as i32;
+
+            (first..=last)
+                .map(|exponent| {
+                    let value = 10f64.powi(exponent);
+                    (value, format_tick(value, value))
+                })
+                .collect()
+        } else {
+            let step = tick_step(self.max - self.min);
+            let count = ((self.max - self.min) / step).round() as usize;
+
+            (0..=count)
+                .map(|i| {
+                    let value = self.min + i as f64 * step;
+                    (value, format_tick(value, step))
+                })
+                .collect()
+        }
+    }
+}
+
+/// Returns a step of 1, 2 or 5 times a power of ten dividing a linear axis
+/// spanning `range` into at most 6 ticks.
+fn tick_step(range: f64) -> f64 {
+    let magnitude = 10f64.powf((range / 6f64).log10().floor());
+
+    [1f64, 2f64, 5f64, 10f64]
+        .iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| range / step <= 6f64)
+        .unwrap_or(10f64 * magnitude)
+}
+
+/// Formats the label of a tick at `value` on an axis with ticks every
+/// `step`, using scientific notation for large values.
+fn format_tick(value: f64, step: f64) -> String {
+    if value.abs() >= 1e6 {
+        format!("{:e}", value)
+    } else {
+        let decimals = (-step.log10().floor()).max(0f64) as usize;
+        format!("{:.*}", decimals, value)
+    }
+}
+
+/// Escapes the characters that have a special meaning in XML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The median, 5th and 95th percentile of the values at a point or bar.
+type Spread = (f64, f64, f64);
+
+/// Returns the spread of the given values, `None` if there aren't any.
+fn spread(values: &[f64]) -> Option<Spread> {
+    Summary::new(values).map(|x| (x.median, x.p5, x.p95))
+}
+
+/// The rectangle the data of a chart is drawn in.
+struct Plot {
+    left: f64,
+    right: f64,
+    top: f64,
+    bottom: f64,
+}
+
+impl Plot {
+    fn x(&self, axis: &Axis, value: f64) -> f64 {
+        self.left + axis.fraction(value) * (self.right - self.left)
+    }
+
+    fn y(&self, axis: &Axis, value: f64) -> f64 {
+        self.bottom - axis.fraction(value) * (self.bottom - self.top)
+    }
+}
+
+/// Starts an SVG document of the given size with a title.
+fn begin_svg(svg: &mut String, width: f64, height: f64, title: &str) {
+    // Writing to a string can't fail, so unwrapping is safe here and below
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+        viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">",
+        w = width,
+        h = height
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n\
+        <text x=\"{}\" y=\"28\" text-anchor=\"middle\" font-size=\"16\">{}</text>",
+        width / 2f64,
+        escape(title)
+    )
+    .unwrap();
+}
+
+/// Draws the y axis with its grid lines, tick labels and label as well as
+/// the frame of the plot.
+fn draw_y_axis(svg: &mut String, plot: &Plot, axis: &Axis, label: &str) {
+    for (value, tick) in axis.ticks() {
+        let y = plot.y(axis, value);
+
+        writeln!(
+            svg,
+            "<line x1=\"{:.1}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\" stroke=\"#dddddd\"/>\n\
+            <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>",
+            plot.left,
+            plot.right,
+            plot.left - 6f64,
+            y + 4f64,
+            tick,
+            y = y
+        )
+        .unwrap();
+    }
+
+    let center = (plot.top + plot.bottom) / 2f64;
+
+    writeln!(
+        svg,
+        "<text x=\"20\" y=\"{c:.1}\" text-anchor=\"middle\" transform=\"rotate(-90 20 {c:.1})\">\
+        {}</text>\n\
+        <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"none\" stroke=\"black\"/>",
+        escape(label),
+        plot.left,
+        plot.top,
+        plot.right - plot.left,
+        plot.bottom - plot.top,
+        c = center
+    )
+    .unwrap();
+}
+
+/// Draws a vertical error bar with caps from `low` to `high`.
+fn draw_error_bar(svg: &mut String, x: f64, low: f64, high: f64, color: &str) {
+    writeln!(
+        svg,
+        "<path d=\"M{l:.1} {low:.1}H{r:.1}M{x:.1} {low:.1}V{high:.1}M{l:.1} {high:.1}H{r:.1}\" \
+        stroke=\"{}\" fill=\"none\"/>",
+        color,
+        l = x - 4f64,
+        r = x + 4f64,
+        x = x,
+        low = low,
+        high = high
+    )
+    .unwrap();
+}
+
+/// Draws a legend entry with the given color right of the plot.
+fn draw_legend_entry(svg: &mut String, plot: &Plot, index: usize, name: &str, color: &str) {
+    let y = plot.top + index as f64 * 20f64;
+
+    writeln!(
+        svg,
+        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"12\" height=\"12\" fill=\"{}\"/>\n\
+        <text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+        plot.right + 16f64,
+        y,
+        color,
+        plot.right + 34f64,
+        y + 10f64,
+        escape(name)
+    )
+    .unwrap();
+}
+
+/// Renders the search times of the given series as a line chart with
+/// error bars.
+fn line_chart(title: &str, x_label: &str, series: &[Series], log_axes: LogAxes) -> String {
+    let points: Vec<(usize, f64, f64, f64)> = series
+        .iter()
+        .flat_map(|x| x.values.iter())
+        .filter_map(|(x, values)| spread(values).map(|(median, p5, p95)| (*x, median, p5, p95)))
+        .collect();
+
+    let x_values: Vec<f64> = points.iter().map(|x| x.0 as f64).collect();
+    let y_values: Vec<f64> = points.iter().flat_map(|x| vec![x.2, x.3]).collect();
+    let x_axis = Axis::new(&x_values, log_axes.x, false);
+    let y_axis = Axis::new(&y_values, log_axes.y, true);
+
+    // The chart grows with the legend if there are many series
+    let height = HEIGHT.max(MARGIN_TOP + series.len() as f64 * 20f64 + MARGIN_BOTTOM);
+
+    let plot = Plot {
+        left: MARGIN_LEFT,
+        right: WIDTH - MARGIN_RIGHT,
+        top: MARGIN_TOP,
+        bottom: height - MARGIN_BOTTOM,
+    };
+
+    let mut svg = String::new();
+    begin_svg(&mut svg, WIDTH, height, title);
+
+    for (value, tick) in x_axis.ticks() {
+        let x = plot.x(&x_axis, value);
+
+        writeln!(
+            svg,
+            "<line x1=\"{x:.1}\" y1=\"{:.1}\" x2=\"{x:.1}\" y2=\"{:.1}\" stroke=\"#dddddd\"/>\n\
+            <text x=\"{x:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+            plot.top,
+            plot.bottom,
+            plot.bottom + 18f64,
+            tick,
+            x = x
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+        (plot.left + plot.right) / 2f64,
+        height - 15f64,
+        escape(x_label)
+    )
+    .unwrap();
+
+    draw_y_axis(&mut svg, &plot, &y_axis, "Search time (ns)");
+
+    for (index, series) in series.iter().enumerate() {
+        let color = COLORS[index % COLORS.len()];
+        let mut path = String::new();
+
+        for (x, values) in series.values.iter() {
+            let (median, p5, p95) = match spread(values) {
+                Some(spread) => spread,
+                None => continue,
+            };
+
+            if !x_axis.contains(*x as f64) || !y_axis.contains(median) {
+                continue;
+            }
+
+            let x = plot.x(&x_axis, *x as f64);
+            let y = plot.y(&y_axis, median);
+
+            write!(
+                path,
+                "{}{:.1} {:.1}",
+                if path.is_empty() { "M" } else { "L" },
+                x,
+                y
+            )
+            .unwrap();
+
+            if y_axis.contains(p5) {
+                draw_error_bar(
+                    &mut svg,
+                    x,
+                    plot.y(&y_axis, p5),
+                    plot.y(&y_axis, p95),
+                    color,
+                );
+            }
+
+            writeln!(
+                svg,
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\"/>",
+                x, y, color
+            )
+            .unwrap();
+        }
+
+        if !path.is_empty() {
+            writeln!(
+                svg,
+                "<path d=\"{}\" stroke=\"{}\" stroke-width=\"2\" fill=\"none\"/>",
+                path, color
+            )
+            .unwrap();
+        }
+
+        draw_legend_entry(&mut svg, &plot, index, &series.name, color);
+    }
+
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+/// Renders the median preparation and search times of the given bars
+/// stacked on top of each other, with an error bar of the total times.
+fn stacked_bar_chart(bars: &[Bar]) -> String {
+    let width = WIDTH.max(MARGIN_LEFT + MARGIN_RIGHT + bars.len() as f64 * BAR_SLOT_WIDTH);
+    let height = HEIGHT - MARGIN_BOTTOM + MARGIN_BOTTOM_BARS;
+
+    // Each bar is drawn from the medians of both phases, its error bar from
+    // the total times of the single executions
+    let stacks: Vec<(f64, f64, Option<Spread>)> = bars
+        .iter()
+        .map(|bar| {
+            let totals: Vec<f64> = bar
+                .preparation
+                .iter()
+                .zip(bar.search.iter())
+                .map(|(a, b)| a + b)
+                .collect();
+
+            (
+                spread(&bar.preparation).map_or(0f64, |x| x.0),
+                spread(&bar.search).map_or(0f64, |x| x.0),
+                spread(&totals),
+            )
+        })
+        .collect();
+
+    let y_values: Vec<f64> = stacks
+        .iter()
+        .flat_map(|(preparation, search, total)| {
+            vec![preparation + search, total.map_or(0f64, |x| x.2)]
+        })
+        .collect();
+    let y_axis = Axis::new(&y_values, false, true);
+
+    let plot = Plot {
+        left: MARGIN_LEFT,
+        right: width - MARGIN_RIGHT,
+        top: MARGIN_TOP,
+        bottom: height - MARGIN_BOTTOM_BARS,
+    };
+    let slot = (plot.right - plot.left) / bars.len().max(1) as f64;
+
+    let mut svg = String::new();
+    begin_svg(
+        &mut svg,
+        width,
+        height,
+        "Preparation and search time by algorithm",
+    );
+    draw_y_axis(&mut svg, &plot, &y_axis, "Time (ns)");
+
+    for (index, (bar, (preparation, search, total))) in bars.iter().zip(stacks.iter()).enumerate() {
+        let center = plot.left + (index as f64 + 0.5) * slot;
+        let left = center - slot * 0.3;
+        let preparation_top = plot.y(&y_axis, *preparation);
+        let search_top = plot.y(&y_axis, preparation + search);
+
+        writeln!(
+            svg,
+            "<rect x=\"{l:.1}\" y=\"{:.1}\" width=\"{w:.1}\" height=\"{:.1}\" fill=\"{}\"/>\n\
+            <rect x=\"{l:.1}\" y=\"{:.1}\" width=\"{w:.1}\" height=\"{:.1}\" fill=\"{}\"/>\n\
+            <text x=\"{c:.1}\" y=\"{:.1}\" \
+            transform=\"rotate(45 {c:.1} {:.1})\">{}</text>",
+            preparation_top,
+            plot.bottom - preparation_top,
+            COLORS[0],
+            search_top,
+            preparation_top - search_top,
+            COLORS[1],
+            plot.bottom + 14f64,
+            plot.bottom + 14f64,
+            escape(&bar.label),
+            l = left,
+            w = slot * 0.6,
+            c = center
+        )
+        .unwrap();
+
+        if let Some((_, p5, p95)) = total {
+            draw_error_bar(
+                &mut svg,
+                center,
+                plot.y(&y_axis, *p5),
+                plot.y(&y_axis, *p95),
+                "black",
+            );
+        }
+    }
+
+    draw_legend_entry(&mut svg, &plot, 0, "Preparation", COLORS[0]);
+    draw_legend_entry(&mut svg, &plot, 1, "Search", COLORS[1]);
+
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(algorithm: &str, pattern_length: usize, time_ns: f64) -> ChartSample {
+        ChartSample {
+            algorithm: algorithm.to_string(),
+            text_length: 1000,
+            pattern_length,
+            preparation_time_ns: 10f64,
+            time_ns,
+        }
+    }
+
+    #[test]
+    fn test_linear_axis() {
+        let axis = Axis::new(&[3f64, 47f64], false, true);
+
+        assert_eq!((axis.min, axis.max), (0f64, 50f64));
+        assert_eq!(axis.fraction(25f64), 0.5);
+        assert_eq!(
+            axis.ticks()
+                .iter()
+                .map(|x| x.1.as_str())
+                .collect::<Vec<_>>(),
+            vec!["0", "10", "20", "30", "40", "50"]
+        );
+    }
+
+    #[test]
+    fn test_log_axis() {
+        let axis = Axis::new(&[0f64, 30f64, 4500f64], true, true);
+
+        assert_eq!((axis.min, axis.max), (10f64, 10000f64));
+        assert!((axis.fraction(100f64) - 1f64 / 3f64).abs() < 1e-9);
+        assert!(!axis.contains(0f64));
+        assert_eq!(axis.ticks().len(), 4);
+    }
+
+    #[test]
+    fn test_log_axes_from_str() {
+        assert_eq!("none".parse(), Ok(LogAxes::default()));
+        assert_eq!("xy".parse(), Ok(LogAxes { x: true, y: true }));
+        assert!("z".parse::<LogAxes>().is_err());
+    }
+
+    #[test]
+    fn test_line_chart() {
+        let samples = vec![
+            sample("Naive", 2, 100f64),
+            sample("Naive", 2, 120f64),
+            sample("Naive", 4, 110f64),
+            sample("A & B", 2, 50f64),
+        ];
+        let series = group(&samples, |x| {
+            (x.algorithm.clone(), x.pattern_length, x.time_ns)
+        });
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].values[&2], vec![100f64, 120f64]);
+
+        let svg = line_chart("Title", "Pattern length", &series, LogAxes::default());
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("A &amp; B"));
+        assert_eq!(svg.matches("<circle").count(), 3);
+    }
+
+    #[test]
+    fn test_read_samples() {
+        let directory = tempfile::tempdir().unwrap();
+        let file_name = directory.path().join("results.csv");
+        let file_name = file_name.to_str().unwrap();
+
+        fs::write(
+            file_name,
+            "algorithm,text_length,pattern_length,status,execution,prep_time_ns,time_ns,outlier\n\
+            SAIS Suffix Array Construction,1000,0,ok,0,500,0,false\n\
+            Naive,1000,3,ok,0,0,250,false\n\
+            Naive,1000,3,ok,1,0,900,true\n\
+            Aho-Corasick,1000,0,ok,0,0,400,false\n\
+            KMP,1000,3,timeout,,,,\n",
+        )
+        .unwrap();
+
+        let sample = |algorithm: &str, pattern_length: usize, time_ns: f64| ChartSample {
+            algorithm: String::from(algorithm),
+            text_length: 1000,
+            pattern_length,
+            preparation_time_ns: 0f64,
+            time_ns,
+        };
+
+        // Multiple pattern algorithms are measured with a pattern length of
+        // 0, too, unlike index constructions they are charted
+        assert_eq!(
+            read_samples(file_name, true).unwrap(),
+            vec![
+                sample("Naive", 3, 250f64),
+                sample("Aho-Corasick", 0, 400f64)
+            ]
+        );
+        assert_eq!(
+            read_samples(file_name, false).unwrap(),
+            vec![
+                sample("Naive", 3, 250f64),
+                sample("Naive", 3, 900f64),
+                sample("Aho-Corasick", 0, 400f64)
+            ]
+        );
+    }
+}
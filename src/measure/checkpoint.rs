@@ -0,0 +1,485 @@
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+
+use csv::{ReaderBuilder, StringRecord};
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::occurrences::QueryMode;
+use crate::cli::{CLIParams, Executions};
+use crate::match_algorithm::{algorithm_argument, match_algorithm};
+use crate::measure::cache::CacheMode;
+use crate::measure::measurement_result::Status;
+use crate::measure::output::OutputFormat;
+
+/// The parameters a measurement has been taken with that affect its
+/// results, so that a measurement is only resumed with the same
+/// configuration.
+///
+/// The text and the patterns are identified by a hash of their contents.
+/// The algorithms aren't part of the configuration, the results file is
+/// checked to contain the results of the measured cells in their order
+/// instead, so that algorithms may be appended when resuming.
+#[derive(Deserialize, PartialEq, Serialize)]
+pub struct Configuration {
+    version: String,
+    counters: bool,
+
+    text_length: usize,
+    text_hash: u64,
+    patterns_hash: u64,
+
+    executions: Executions,
+    warmup_executions: Option<usize>,
+    confidence_interval_width: f64,
+    cell_time_limit: f64,
+    track_memory: bool,
+    isolate: bool,
+    execution_timeout: Option<f64>,
+    cell_timeout: Option<f64>,
+    pinned_cpu: Option<usize>,
+    threads: usize,
+    query_mode: Option<QueryMode>,
+    cache_mode: Option<CacheMode>,
+    aggregate: bool,
+
+    suffix_array_algorithm: String,
+    occ_block_size: usize,
+    maximum_error: Option<usize>,
+}
+
+impl Configuration {
+    pub fn new(text: &[u8], patterns: &[Vec<u8>], cli_params: &CLIParams) -> Self {
+        Self {
+            version: String::from(env!("CARGO_PKG_VERSION")),
+            counters: cfg!(feature = "counters"),
+
+            text_length: text.len(),
+            text_hash: fnv1a(FNV_OFFSET_BASIS, text),
+            patterns_hash: patterns.iter().fold(FNV_OFFSET_BASIS, |hash, pattern| {
+                // Hash the length, too, so that the boundaries between the
+                // patterns matter
+                fnv1a(fnv1a(hash, &pattern.len().to_le_bytes()), pattern)
+            }),
+
+            executions: cli_params.executions,
+            warmup_executions: cli_params.warmup_executions,
+            confidence_interval_width: cli_params.confidence_interval_width,
+            cell_time_limit: cli_params.cell_time_limit,
+            track_memory: cli_params.track_memory,
+            isolate: cli_params.isolate,
+            execution_timeout: cli_params.execution_timeout,
+            cell_timeout: cli_params.cell_timeout,
+            pinned_cpu: cli_params.pinned_cpu,
+            threads: cli_params.threads,
+            query_mode: cli_params.query_mode,
+            cache_mode: cli_params.cache_mode,
+            aggregate: cli_params.aggregate,
+
+            suffix_array_algorithm: cli_params.suffix_array_algorithm.clone(),
+            occ_block_size: cli_params.occ_block_size,
+            maximum_error: cli_params.maximum_error,
+        }
+    }
+}
+
+/// Writes the configuration of a measurement to a new checkpoint file with
+/// the given name, so that the results of the measurement can be continued
+/// after a crash or abort.
+///
+/// If `resume` is set and the file exists already, it is checked that it
+/// has been written with the same configuration instead. The file is never
+/// overwritten, so it returns an error if it exists but `resume` isn't set,
+/// if the existing file has been written with a different configuration or
+/// if the file can't be read or written.
+///
+/// It returns whether an existing checkpoint file is resumed.
+pub fn open_checkpoint(
+    file_name: &str,
+    resume: bool,
+    configuration: &Configuration,
+) -> Result<bool, String> {
+    if resume && Path::new(file_name).exists() {
+        let stored_configuration: Option<Configuration> = fs::read_to_string(file_name)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok());
+
+        return if stored_configuration.as_ref() == Some(configuration) {
+            Ok(true)
+        } else {
+            Err(format!(
+                "The checkpoint file {} has been written with a different configuration \
+                or is no checkpoint file",
+                file_name
+            ))
+        };
+    }
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(file_name)
+        .map_err(|error| match error.kind() {
+            ErrorKind::AlreadyExists => format!(
+                "The checkpoint file {} exists already, use --resume to continue the \
+                measurement stored in it",
+                file_name
+            ),
+            _ => format!("{}: {}", file_name, error),
+        })?;
+
+    serde_json::to_writer(&mut file, configuration).map_err(|error| error.to_string())?;
+
+    file.write_all(b"\n")
+        .map(|_| false)
+        .map_err(|error| error.to_string())
+}
+
+/// The cells of an aborted measurement whose results have been written to
+/// the results file completely.
+///
+/// As the results are written in the order of the cells, these are the
+/// first cells of the measurement.
+#[derive(Default)]
+pub struct ResumedResults {
+    /// The statuses of the completed cells.
+    pub statuses: Vec<Status>,
+    /// The CLI parameter names of the index constructions reported in the
+    /// results of the completed cells.
+    pub constructions: HashSet<String>,
+    /// The length of the results file up to the end of the completed cells.
+    pub length: u64,
+}
+
+/// The columns of a result record identifying the cell it belongs to.
+#[derive(Deserialize)]
+struct ResultRecord {
+    algorithm: String,
+    text_length: usize,
+    pattern_length: usize,
+    status: Status,
+    /// The index of the execution, `None` for failed measurements and
+    /// summary records.
+    execution: Option<usize>,
+    executions: usize,
+}
+
+/// A JSON result record, reduced to the fields identifying its cell.
+#[derive(Deserialize)]
+struct JSONResultRecord {
+    algorithm: JSONName,
+    text: JSONInput,
+    pattern: JSONInput,
+    status: Status,
+    execution: Option<usize>,
+    executions: usize,
+}
+
+#[derive(Deserialize)]
+struct JSONName {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct JSONInput {
+    length: usize,
+}
+
+impl From<JSONResultRecord> for ResultRecord {
+    fn from(record: JSONResultRecord) -> Self {
+        Self {
+            algorithm: record.algorithm.name,
+            text_length: record.text.length,
+            pattern_length: record.pattern.length,
+            status: record.status,
+            execution: record.execution,
+            executions: record.executions,
+        }
+    }
+}
+
+impl ResumedResults {
+    /// Reads the cells completed by an aborted measurement from the results
+    /// file with the given name, written in CSV (with a header) or JSON lines
+    /// `format`.
+    ///
+    /// The `cells` are the display name of the algorithm and the length of
+    /// the pattern of each cell of the measurement in their order. Results
+    /// of a cell that has only been measured partially and of a skipped cell
+    /// are discarded together with all results after them, so that these
+    /// cells are measured again. A missing results file contains no results.
+    ///
+    /// It returns an error if the file can't be read or contains results
+    /// that don't belong to the given cells.
+    pub fn read(
+        file_name: &str,
+        format: OutputFormat,
+        text_length: usize,
+        cells: &[(&str, usize)],
+    ) -> Result<Self, String> {
+        let contents = match fs::read(file_name) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(format!("{}: {}", file_name, error)),
+        };
+
+        // Only the last line can be incomplete, so it is ignored
+        let complete_lines = contents
+            .iter()
+            .rposition(|x| *x == b'\n')
+            .map_or(&contents[..0], |i| &contents[..=i]);
+
+        let (header_length, records) = match format {
+            OutputFormat::Csv => csv_records(complete_lines).map_err(|error| error.to_string()),
+            _ => jsonl_records(complete_lines).map_err(|error| error.to_string()),
+        }
+        .map_err(|error| format!("{}: {}", file_name, error))?;
+
+        let mut resumed_results = Self {
+            length: header_length,
+            ..Self::default()
+        };
+        let mut constructions = Vec::new();
+
+        for (record, end) in records {
+            let (algorithm, pattern_length) = match cells.get(resumed_results.statuses.len()) {
+                Some(cell) => *cell,
+                None => return Err(foreign_results(file_name)),
+            };
+
+            // Index constructions are reported before the first cell
+            // requiring them
+            if record.algorithm != algorithm {
+                match algorithm_argument(&record.algorithm) {
+                    Some(argument) if match_algorithm(argument).is_none() => {
+                        constructions.push(argument.to_string());
+                        continue;
+                    }
+                    _ => return Err(foreign_results(file_name)),
+                }
+            }
+
+            if record.text_length != text_length || record.pattern_length != pattern_length {
+                return Err(foreign_results(file_name));
+            }
+
+            if record.status == Status::Skipped {
+                break;
+            }
+
+            // A cell is complete once its last execution has been written
+            if record.execution.is_none_or(|x| x + 1 == record.executions) {
+                resumed_results.statuses.push(record.status);
+                resumed_results
+                    .constructions
+                    .extend(constructions.drain(..));
+                resumed_results.length = end;
+            }
+        }
+
+        Ok(resumed_results)
+    }
+}
+
+/// Parses the records of a CSV results file, returning the length of the
+/// header and each record with the offset of its end.
+fn csv_records(contents: &[u8]) -> Result<(u64, Vec<(ResultRecord, u64)>), csv::Error> {
+    let mut reader = ReaderBuilder::new().from_reader(contents);
+    let headers = reader.headers()?.clone();
+    let header_length = reader.position().byte();
+
+    let mut records = Vec::new();
+    let mut record = StringRecord::new();
+
+    while reader.read_record(&mut record)? {
+        records.push((
+            record.deserialize(Some(&headers))?,
+            reader.position().byte(),
+        ));
+    }
+
+    Ok((header_length, records))
+}
+
+/// Parses the records of a JSON lines results file, returning each record
+/// with the offset of its end.
+fn jsonl_records(contents: &[u8]) -> Result<(u64, Vec<(ResultRecord, u64)>), serde_json::Error> {
+    let mut records = Vec::new();
+    let mut end = 0;
+
+    for line in contents.split_inclusive(|x| *x == b'\n') {
+        let record: JSONResultRecord = serde_json::from_slice(line)?;
+        end += line.len() as u64;
+
+        records.push((record.into(), end));
+    }
+
+    Ok((0, records))
+}
+
+fn foreign_results(file_name: &str) -> String {
+    format!(
+        "The results file {} doesn't contain the results of this measurement",
+        file_name
+    )
+}
+
+/// The initial value of the 64-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The prime the 64-bit FNV-1a hash is multiplied with for each byte.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Continues the 64-bit FNV-1a hash `hash` with the given bytes.
+///
+/// Unlike the hashers of the standard library, its values are guaranteed
+/// to be the same for all versions of Rust, so that they can be stored.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configuration(text: &[u8]) -> Configuration {
+        Configuration {
+            version: String::from(env!("CARGO_PKG_VERSION")),
+            counters: false,
+
+            text_length: text.len(),
+            text_hash: fnv1a(FNV_OFFSET_BASIS, text),
+            patterns_hash: FNV_OFFSET_BASIS,
+
+            executions: Executions::Fixed(3),
+            warmup_executions: Some(0),
+            confidence_interval_width: 0.05,
+            cell_time_limit: 10f64,
+            track_memory: false,
+            isolate: false,
+            execution_timeout: None,
+            cell_timeout: None,
+            pinned_cpu: None,
+            threads: 1,
+            query_mode: Some(QueryMode::All),
+            cache_mode: Some(CacheMode::None),
+            aggregate: false,
+
+            suffix_array_algorithm: String::from("sais"),
+            occ_block_size: 1,
+            maximum_error: Some(0),
+        }
+    }
+
+    #[test]
+    fn test_open_checkpoint() {
+        let directory = tempfile::tempdir().unwrap();
+        let file_name = directory.path().join("checkpoint.json");
+        let file_name = file_name.to_str().unwrap();
+
+        assert_eq!(
+            open_checkpoint(file_name, true, &configuration(b"abc")),
+            Ok(false)
+        );
+
+        let contents = fs::read_to_string(file_name).unwrap();
+
+        assert!(open_checkpoint(file_name, false, &configuration(b"abc")).is_err());
+        assert!(open_checkpoint(file_name, true, &configuration(b"abd")).is_err());
+        assert_eq!(
+            open_checkpoint(file_name, true, &configuration(b"abc")),
+            Ok(true)
+        );
+
+        // The existing file is never overwritten
+        assert_eq!(fs::read_to_string(file_name).unwrap(), contents);
+    }
+
+    #[test]
+    fn test_read_resumed_results() {
+        let directory = tempfile::tempdir().unwrap();
+        let file_name = directory.path().join("results.csv");
+        let file_name = file_name.to_str().unwrap();
+
+        let header = "algorithm,text_length,pattern_length,status,execution,executions\n";
+        let naive = "Naive,3,1,ok,0,2\nNaive,3,1,ok,1,2\n";
+        let timeout = "Horspool,3,2,timeout,,0\n";
+        let construction = "SAIS Suffix Array Construction,3,0,ok,0,1\n";
+        let partial = "SA Pattern Matching,3,1,ok,0,2\nSA Pattern Matching,3,1,o";
+
+        let cells = [
+            ("Naive", 1),
+            ("Horspool", 2),
+            ("SA Pattern Matching", 1),
+            ("SA Pattern Matching", 2),
+        ];
+        let read = |contents: &str, format: OutputFormat| {
+            fs::write(file_name, contents).unwrap();
+
+            ResumedResults::read(file_name, format, 3, &cells)
+        };
+
+        // The construction and the partially measured cell are discarded
+        let resumed_results = read(
+            &[header, naive, timeout, construction, partial].concat(),
+            OutputFormat::Csv,
+        )
+        .unwrap();
+
+        assert_eq!(resumed_results.statuses, vec![Status::Ok, Status::Timeout]);
+        assert!(resumed_results.constructions.is_empty());
+        assert_eq!(
+            resumed_results.length,
+            (header.len() + naive.len() + timeout.len()) as u64
+        );
+
+        let complete = "SA Pattern Matching,3,1,ok,1,2\nSA Pattern Matching,3,2,skipped,,0\n";
+        let resumed_results = read(
+            &[
+                header,
+                naive,
+                timeout,
+                construction,
+                &partial[..31],
+                complete,
+            ]
+            .concat(),
+            OutputFormat::Csv,
+        )
+        .unwrap();
+
+        // Skipped cells are measured again
+        assert_eq!(resumed_results.statuses.len(), 3);
+        assert!(resumed_results.constructions.contains("sa-sais"));
+
+        // Results of other cells can't be continued
+        assert!(read(&[header, timeout].concat(), OutputFormat::Csv).is_err());
+        assert!(read(&[header, naive, naive, naive].concat(), OutputFormat::Csv).is_err());
+
+        let jsonl = "{\"algorithm\":{\"name\":\"Naive\"},\"text\":{\"length\":3},\
+            \"pattern\":{\"length\":1},\"status\":\"ok\",\"execution\":0,\"executions\":1}\n";
+        let resumed_results = read(&[jsonl, &jsonl[..20]].concat(), OutputFormat::Jsonl).unwrap();
+
+        assert_eq!(resumed_results.statuses, vec![Status::Ok]);
+        assert_eq!(resumed_results.length, jsonl.len() as u64);
+
+        // A missing results file is started from scratch
+        fs::remove_file(file_name).unwrap();
+
+        let resumed_results =
+            ResumedResults::read(file_name, OutputFormat::Csv, 3, &cells).unwrap();
+
+        assert!(resumed_results.statuses.is_empty());
+        assert_eq!(resumed_results.length, 0);
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b""), FNV_OFFSET_BASIS);
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"foobar"), 0x85944171f73967e8);
+    }
+}
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "counters")]
+use std::cell::Cell;
+
+/// The hardware-independent operations performed by a single pattern
+/// algorithm while searching a text.
+///
+/// The operations are only counted if the program has been built with the
+/// `counters` feature. Otherwise, the counting functions of this module
+/// compile to nothing, so they don't distort the runtime measurements.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OperationCounts {
+    /// The number of comparisons of a text character with a pattern
+    /// character.
+    pub comparisons: usize,
+    /// The number of text characters read, including characters read
+    /// multiple times.
+    pub inspections: usize,
+    /// The number of times the pattern has been shifted along the text.
+    pub shifts: usize,
+    /// The total number of characters the pattern has been shifted by.
+    pub shift_distance: usize,
+}
+
+impl OperationCounts {
+    /// Returns the average number of characters the pattern has been shifted
+    /// by, `None` if it hasn't been shifted at all.
+    pub fn mean_shift(&self) -> Option<f64> {
+        if self.shifts == 0 {
+            None
+        } else {
+            Some(self.shift_distance as f64 / self.shifts as f64)
+        }
+    }
+}
+
+#[cfg(feature = "counters")]
+thread_local! {
+    static COUNTS: Cell<OperationCounts> = const {
+        Cell::new(OperationCounts {
+            comparisons: 0,
+            inspections: 0,
+            shifts: 0,
+            shift_distance: 0,
+        })
+    };
+}
+
+/// Applies `update` to the operation counts of the current thread.
+#[cfg(feature = "counters")]
+fn add(update: impl FnOnce(&mut OperationCounts)) {
+    COUNTS.with(|counts| {
+        let mut current = counts.get();
+        update(&mut current);
+        counts.set(current);
+    });
+}
+
+/// Counts a text character `c` as inspected and returns it.
+#[inline(always)]
+pub fn inspect(c: u8) -> u8 {
+    #[cfg(feature = "counters")]
+    add(|counts| counts.inspections += 1);
+
+    c
+}
+
+/// Counts a comparison of the characters `a` and `b` and returns whether
+/// they are equal.
+#[inline(always)]
+pub fn compare(a: u8, b: u8) -> bool {
+    #[cfg(feature = "counters")]
+    add(|counts| counts.comparisons += 1);
+
+    a == b
+}
+
+/// Compares a `window` of the text with a `pattern` of the same length
+/// from left to right and returns whether they are equal.
+///
+/// Each compared character is counted as a comparison and as an inspected
+/// text character, the comparison stops at the first mismatch.
+#[inline(always)]
+pub fn compare_window(window: &[u8], pattern: &[u8]) -> bool {
+    #[cfg(feature = "counters")]
+    {
+        let compared = window
+            .iter()
+            .zip(pattern)
+            .position(|(a, b)| a != b)
+            .map_or(window.len().min(pattern.len()), |i| i + 1);
+
+        add(|counts| {
+            counts.comparisons += compared;
+            counts.inspections += compared;
+        });
+    }
+
+    window == pattern
+}
+
+/// Counts a shift of the pattern by `distance` characters and returns the
+/// distance.
+#[inline(always)]
+pub fn shift(distance: usize) -> usize {
+    #[cfg(feature = "counters")]
+    add(|counts| {
+        counts.shifts += 1;
+        counts.shift_distance += distance;
+    });
+
+    distance
+}
+
+/// Executes `f` while counting the operations performed by the current
+/// thread.
+///
+/// It returns the result of `f` and the operations counted during its
+/// execution.
+#[cfg(feature = "counters")]
+pub fn count_operations<R, F: FnOnce() -> R>(f: F) -> (R, Option<OperationCounts>) {
+    COUNTS.with(|counts| counts.set(OperationCounts::default()));
+
+    let result = f();
+
+    (result, Some(COUNTS.with(|counts| counts.get())))
+}
+
+/// Executes `f` without counting any operations, as the program has been
+/// built without the `counters` feature.
+///
+/// It returns the result of `f` and `None`.
+#[cfg(not(feature = "counters"))]
+pub fn count_operations<R, F: FnOnce() -> R>(f: F) -> (R, Option<OperationCounts>) {
+    (f(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_operations() {
+        let (equal, counts) = count_operations(|| {
+            let equal = compare_window(b"abc", b"abc") && compare_window(b"xbc", b"abc");
+            shift(3);
+            shift(2);
+
+            equal
+        });
+
+        assert!(!equal);
+
+        if cfg!(feature = "counters") {
+            let counts = counts.unwrap();
+
+            // The second window is only compared up to its first character
+            assert_eq!(counts.comparisons, 4);
+            assert_eq!(counts.inspections, 4);
+            assert_eq!(counts.shifts, 2);
+            assert_eq!(counts.mean_shift(), Some(2.5));
+        } else {
+            assert!(counts.is_none());
+        }
+    }
+}
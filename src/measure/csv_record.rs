@@ -1,5 +1,13 @@
 use serde::Serialize;
 
+use crate::algorithms::occurrences::QueryMode;
+use crate::measure::cache::CacheMode;
+use crate::measure::counters::OperationCounts;
+use crate::measure::measurement_result::Status;
+use crate::measure::memory::MemoryUsage;
+use crate::measure::statistics::Summary;
+use crate::measure::throughput::Throughput;
+
 #[derive(Serialize)]
 pub struct CSVRecord<'a> {
     #[serde(rename = "algorithm")]
@@ -10,28 +18,84 @@ pub struct CSVRecord<'a> {
     #[serde(rename = "pattern_length")]
     pattern_length: usize,
 
+    #[serde(rename = "status")]
+    status: Status,
+    #[serde(rename = "cpu")]
+    cpu: Option<usize>,
+    #[serde(rename = "threads")]
+    threads: usize,
+    #[serde(rename = "query")]
+    query_mode: QueryMode,
+    #[serde(rename = "cache")]
+    cache_mode: CacheMode,
+
     #[serde(rename = "execution")]
-    execution: usize,
+    execution: Option<usize>,
+    #[serde(rename = "executions")]
+    executions: usize,
 
     #[serde(rename = "matches")]
-    matches: usize,
+    matches: Option<usize>,
+
+    #[serde(rename = "prep_time_ns")]
+    preparation_time_ns: Option<u128>,
+
+    #[serde(rename = "time_ns")]
+    time_ns: Option<u128>,
+
+    #[serde(rename = "prep_peak_bytes")]
+    preparation_peak_bytes: Option<usize>,
+    #[serde(rename = "prep_allocations")]
+    preparation_allocations: Option<usize>,
+    #[serde(rename = "peak_bytes")]
+    peak_bytes: Option<usize>,
+    #[serde(rename = "allocations")]
+    allocations: Option<usize>,
 
-    #[serde(rename = "prep_time_ms")]
-    preparation_time_ms: u128,
+    #[serde(rename = "comparisons")]
+    comparisons: Option<usize>,
+    #[serde(rename = "inspected_chars")]
+    inspections: Option<usize>,
+    #[serde(rename = "shifts")]
+    shifts: Option<usize>,
+    #[serde(rename = "shift_distance")]
+    shift_distance: Option<usize>,
+    #[serde(rename = "mean_shift")]
+    mean_shift: Option<f64>,
 
-    #[serde(rename = "time_ms")]
-    time_ms: u128,
+    #[serde(rename = "mb_per_s")]
+    mb_per_s: Option<f64>,
+    #[serde(rename = "ns_per_byte")]
+    ns_per_byte: Option<f64>,
+    #[serde(rename = "ns_per_pattern_char")]
+    ns_per_pattern_char: Option<f64>,
+    #[serde(rename = "ns_per_occurrence")]
+    ns_per_occurrence: Option<f64>,
+
+    #[serde(rename = "outlier")]
+    outlier: Option<bool>,
 }
 
 impl<'a> CSVRecord<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         algorithm_name: &'a str,
         text_length: usize,
         pattern_length: usize,
         execution: usize,
+        executions: usize,
         matches: usize,
-        preparation_time_ms: u128,
-        time_ms: u128,
+        preparation_time_ns: u128,
+        time_ns: u128,
+        preparation_memory: Option<MemoryUsage>,
+        algorithm_memory: Option<MemoryUsage>,
+        counts: Option<OperationCounts>,
+        throughput: Throughput,
+        outlier: bool,
+        cpu: Option<usize>,
+        threads: usize,
+        query_mode: QueryMode,
+        cache_mode: CacheMode,
     ) -> Self {
         Self {
             algorithm_name,
@@ -39,12 +103,281 @@ impl<'a> CSVRecord<'a> {
             text_length,
             pattern_length,
 
-            execution,
+            status: Status::Ok,
+            cpu,
+            threads,
+            query_mode,
+            cache_mode,
+
+            execution: Some(execution),
+            executions,
+
+            matches: Some(matches),
+
+            preparation_time_ns: Some(preparation_time_ns),
+            time_ns: Some(time_ns),
+
+            preparation_peak_bytes: preparation_memory.map(|x| x.peak_bytes),
+            preparation_allocations: preparation_memory.map(|x| x.allocations),
+            peak_bytes: algorithm_memory.map(|x| x.peak_bytes),
+            allocations: algorithm_memory.map(|x| x.allocations),
+
+            comparisons: counts.map(|x| x.comparisons),
+            inspections: counts.map(|x| x.inspections),
+            shifts: counts.map(|x| x.shifts),
+            shift_distance: counts.map(|x| x.shift_distance),
+            mean_shift: counts.and_then(|x| x.mean_shift()),
+
+            mb_per_s: throughput.mb_per_s,
+            ns_per_byte: throughput.ns_per_byte,
+            ns_per_pattern_char: throughput.ns_per_pattern_char,
+            ns_per_occurrence: throughput.ns_per_occurrence,
+
+            outlier: Some(outlier),
+        }
+    }
+
+    /// Creates a record of an algorithm that couldn't be measured because of
+    /// the given failure `status`, leaving all measured values empty.
+    #[allow(clippy::too_many_arguments)]
+    pub fn failed(
+        algorithm_name: &'a str,
+        text_length: usize,
+        pattern_length: usize,
+        status: Status,
+        cpu: Option<usize>,
+        threads: usize,
+        query_mode: QueryMode,
+        cache_mode: CacheMode,
+    ) -> Self {
+        Self {
+            algorithm_name,
+
+            text_length,
+            pattern_length,
+
+            status,
+            cpu,
+            threads,
+            query_mode,
+            cache_mode,
+
+            execution: None,
+            executions: 0,
+
+            matches: None,
+
+            preparation_time_ns: None,
+            time_ns: None,
+
+            preparation_peak_bytes: None,
+            preparation_allocations: None,
+            peak_bytes: None,
+            allocations: None,
+
+            comparisons: None,
+            inspections: None,
+            shifts: None,
+            shift_distance: None,
+            mean_shift: None,
+
+            mb_per_s: None,
+            ns_per_byte: None,
+            ns_per_pattern_char: None,
+            ns_per_occurrence: None,
+
+            outlier: None,
+        }
+    }
+}
+
+/// A record containing the summary statistics of all executions of an
+/// algorithm with one pattern, all durations given in nanoseconds.
+///
+/// Depending on the CLI parameters, the statistics might not include the
+/// executions which have been classified as outliers.
+///
+/// The preparation columns are empty for algorithms without a preparation
+/// phase and the confidence interval columns are empty if there are less than
+/// two executions. The normalized metrics are derived from the mean runtime.
+///
+/// The memory columns contain the maximum of all executions and are empty if
+/// memory tracking is disabled. The operation count columns are empty unless
+/// the program has been built with the `counters` feature, they are the same
+/// for all executions.
+#[derive(Serialize)]
+pub struct SummaryCSVRecord<'a> {
+    #[serde(rename = "algorithm")]
+    algorithm_name: &'a str,
+
+    #[serde(rename = "text_length")]
+    text_length: usize,
+    #[serde(rename = "pattern_length")]
+    pattern_length: usize,
+
+    #[serde(rename = "status")]
+    status: Status,
+    #[serde(rename = "cpu")]
+    cpu: Option<usize>,
+    #[serde(rename = "threads")]
+    threads: usize,
+    #[serde(rename = "query")]
+    query_mode: QueryMode,
+    #[serde(rename = "cache")]
+    cache_mode: CacheMode,
+
+    #[serde(rename = "executions")]
+    executions: usize,
+    #[serde(rename = "outliers")]
+    outliers: usize,
+
+    #[serde(rename = "matches")]
+    matches: Option<usize>,
+
+    #[serde(rename = "prep_mean_ns")]
+    preparation_mean: Option<f64>,
+    #[serde(rename = "prep_median_ns")]
+    preparation_median: Option<f64>,
+    #[serde(rename = "prep_min_ns")]
+    preparation_min: Option<f64>,
+    #[serde(rename = "prep_max_ns")]
+    preparation_max: Option<f64>,
+    #[serde(rename = "prep_stddev_ns")]
+    preparation_standard_deviation: Option<f64>,
+    #[serde(rename = "prep_p5_ns")]
+    preparation_p5: Option<f64>,
+    #[serde(rename = "prep_p95_ns")]
+    preparation_p95: Option<f64>,
+    #[serde(rename = "prep_ci95_low_ns")]
+    preparation_ci_low: Option<f64>,
+    #[serde(rename = "prep_ci95_high_ns")]
+    preparation_ci_high: Option<f64>,
+
+    #[serde(rename = "time_mean_ns")]
+    mean: Option<f64>,
+    #[serde(rename = "time_median_ns")]
+    median: Option<f64>,
+    #[serde(rename = "time_min_ns")]
+    min: Option<f64>,
+    #[serde(rename = "time_max_ns")]
+    max: Option<f64>,
+    #[serde(rename = "time_stddev_ns")]
+    standard_deviation: Option<f64>,
+    #[serde(rename = "time_p5_ns")]
+    p5: Option<f64>,
+    #[serde(rename = "time_p95_ns")]
+    p95: Option<f64>,
+    #[serde(rename = "time_ci95_low_ns")]
+    ci_low: Option<f64>,
+    #[serde(rename = "time_ci95_high_ns")]
+    ci_high: Option<f64>,
+
+    #[serde(rename = "prep_peak_bytes")]
+    preparation_peak_bytes: Option<usize>,
+    #[serde(rename = "prep_allocations")]
+    preparation_allocations: Option<usize>,
+    #[serde(rename = "peak_bytes")]
+    peak_bytes: Option<usize>,
+    #[serde(rename = "allocations")]
+    allocations: Option<usize>,
+
+    #[serde(rename = "comparisons")]
+    comparisons: Option<usize>,
+    #[serde(rename = "inspected_chars")]
+    inspections: Option<usize>,
+    #[serde(rename = "shifts")]
+    shifts: Option<usize>,
+    #[serde(rename = "shift_distance")]
+    shift_distance: Option<usize>,
+    #[serde(rename = "mean_shift")]
+    mean_shift: Option<f64>,
+
+    #[serde(rename = "mb_per_s")]
+    mb_per_s: Option<f64>,
+    #[serde(rename = "ns_per_byte")]
+    ns_per_byte: Option<f64>,
+    #[serde(rename = "ns_per_pattern_char")]
+    ns_per_pattern_char: Option<f64>,
+    #[serde(rename = "ns_per_occurrence")]
+    ns_per_occurrence: Option<f64>,
+}
+
+impl<'a> SummaryCSVRecord<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        algorithm_name: &'a str,
+        text_length: usize,
+        pattern_length: usize,
+        executions: usize,
+        outliers: usize,
+        matches: Option<usize>,
+        status: Status,
+        cpu: Option<usize>,
+        threads: usize,
+        query_mode: QueryMode,
+        cache_mode: CacheMode,
+        preparation_summary: Option<&Summary>,
+        algorithm_summary: Option<&Summary>,
+        preparation_memory: Option<MemoryUsage>,
+        algorithm_memory: Option<MemoryUsage>,
+        counts: Option<OperationCounts>,
+        throughput: Throughput,
+    ) -> Self {
+        let preparation_ci = preparation_summary.and_then(|x| x.confidence_interval);
+        let ci = algorithm_summary.and_then(|x| x.confidence_interval);
+
+        Self {
+            algorithm_name,
+
+            text_length,
+            pattern_length,
+
+            status,
+            cpu,
+            threads,
+            query_mode,
+            cache_mode,
+
+            executions,
+            outliers,
 
             matches,
 
-            preparation_time_ms,
-            time_ms,
+            preparation_mean: preparation_summary.map(|x| x.mean),
+            preparation_median: preparation_summary.map(|x| x.median),
+            preparation_min: preparation_summary.map(|x| x.min),
+            preparation_max: preparation_summary.map(|x| x.max),
+            preparation_standard_deviation: preparation_summary.map(|x| x.standard_deviation),
+            preparation_p5: preparation_summary.map(|x| x.p5),
+            preparation_p95: preparation_summary.map(|x| x.p95),
+            preparation_ci_low: preparation_ci.map(|x| x.0),
+            preparation_ci_high: preparation_ci.map(|x| x.1),
+
+            mean: algorithm_summary.map(|x| x.mean),
+            median: algorithm_summary.map(|x| x.median),
+            min: algorithm_summary.map(|x| x.min),
+            max: algorithm_summary.map(|x| x.max),
+            standard_deviation: algorithm_summary.map(|x| x.standard_deviation),
+            p5: algorithm_summary.map(|x| x.p5),
+            p95: algorithm_summary.map(|x| x.p95),
+            ci_low: ci.map(|x| x.0),
+            ci_high: ci.map(|x| x.1),
+
+            preparation_peak_bytes: preparation_memory.map(|x| x.peak_bytes),
+            preparation_allocations: preparation_memory.map(|x| x.allocations),
+            peak_bytes: algorithm_memory.map(|x| x.peak_bytes),
+            allocations: algorithm_memory.map(|x| x.allocations),
+
+            comparisons: counts.map(|x| x.comparisons),
+            inspections: counts.map(|x| x.inspections),
+            shifts: counts.map(|x| x.shifts),
+            shift_distance: counts.map(|x| x.shift_distance),
+            mean_shift: counts.and_then(|x| x.mean_shift()),
+
+            mb_per_s: throughput.mb_per_s,
+            ns_per_byte: throughput.ns_per_byte,
+            ns_per_pattern_char: throughput.ns_per_pattern_char,
+            ns_per_occurrence: throughput.ns_per_occurrence,
         }
     }
 }
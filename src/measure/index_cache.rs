@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use crate::algorithms::full_text_indices::full_text_index::{BWTIndex, FullTextIndex};
+use crate::cli::CLIParams;
+use crate::match_algorithm::match_suffix_array_gen_algorithm;
+use crate::measure::measurement::{measure_phase, PhaseMeasurement};
+
+/// A cache of the full-text indices of the measured text.
+///
+/// Each index is only built once per suffix array generation algorithm and
+/// then reused for all patterns and executions, so that only the queries
+/// have to be measured. The measurements of the index constructions are
+/// recorded so that they can be reported once.
+#[derive(Default)]
+pub struct IndexCache {
+    indices: HashMap<String, FullTextIndex>,
+    constructions: Vec<(String, PhaseMeasurement)>,
+}
+
+impl IndexCache {
+    /// Returns the index of the given text built using the suffix array
+    /// generation algorithm given in the CLI parameters, building and
+    /// measuring it first if it is not cached yet.
+    ///
+    /// If `with_bwt` is set, the Occ and less arrays of the text's BWT are
+    /// built as well if they haven't been built before.
+    pub fn get(&mut self, text: &[u8], cli_params: &CLIParams, with_bwt: bool) -> &FullTextIndex {
+        let suffix_array_algorithm = &cli_params.suffix_array_algorithm;
+        let constructions = &mut self.constructions;
+
+        let index = self
+            .indices
+            .entry(suffix_array_algorithm.to_string())
+            .or_insert_with(|| {
+                // Unwrapping is safe here because of the checks done in cli_params.valid()
+                let suffix_array_gen_algorithm =
+                    match_suffix_array_gen_algorithm(suffix_array_algorithm).unwrap();
+
+                let (index, phase) = measure_phase(cli_params, || {
+                    FullTextIndex::new(text, suffix_array_gen_algorithm)
+                });
+
+                constructions.push((format!("sa-{}", suffix_array_algorithm), phase));
+
+                index
+            });
+
+        if with_bwt && index.bwt_index.is_none() {
+            let (bwt_index, phase) =
+                measure_phase(cli_params, || BWTIndex::new(&index.text, &index.pos));
+
+            constructions.push((String::from("bwt-index"), phase));
+
+            index.bwt_index = Some(bwt_index);
+        }
+
+        index
+    }
+
+    /// Returns the names and measurements of all index constructions since
+    /// the last call of this function.
+    ///
+    /// The names are the internal names of the construction algorithms
+    /// which can be passed to `algorithm_name()`.
+    pub fn take_constructions(&mut self) -> Vec<(String, PhaseMeasurement)> {
+        self.constructions.drain(..).collect()
+    }
+}
@@ -0,0 +1,341 @@
+use std::env;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+#[cfg(unix)]
+use std::os::unix::process::ExitStatusExt;
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use crate::cli::CLIParams;
+use crate::match_algorithm::match_algorithm;
+use crate::measure::index_cache::IndexCache;
+use crate::measure::measurement::{
+    measure_cell, CellEvent, CellOutcome, PhaseMeasurement, SingleMeasurement,
+};
+use crate::measure::measurement_result::Status;
+
+/// The exit code of a process after a panic if panics unwind.
+const PANIC_EXIT_CODE: i32 = 101;
+
+/// The signal terminating a process after a panic if panics abort, as they
+/// do in release builds, or after a failed allocation.
+const SIGABRT: i32 = 6;
+
+/// The signal used by the kernel to kill processes if it runs out of memory.
+const SIGKILL: i32 = 9;
+
+/// A cell measured by a child process, as given by the hidden
+/// `--isolatedcell` CLI argument.
+pub struct IsolatedCell {
+    pub algorithm: String,
+    /// The index of the pattern or `None` for multiple pattern algorithms.
+    pub pattern_index: Option<usize>,
+    pub text_file: PathBuf,
+    pub patterns_file: PathBuf,
+}
+
+/// A message a child process sends to its parent, one per line of its
+/// standard output.
+#[derive(Deserialize, Serialize)]
+enum CellMessage {
+    Construction(String, PhaseMeasurement),
+    WarmUp,
+    Execution(SingleMeasurement),
+}
+
+impl From<CellEvent<'_>> for CellMessage {
+    fn from(event: CellEvent) -> Self {
+        match event {
+            CellEvent::Construction(name, phase) => Self::Construction(name.to_string(), *phase),
+            CellEvent::WarmUp => Self::WarmUp,
+            CellEvent::Execution(measurement) => Self::Execution(measurement.clone()),
+        }
+    }
+}
+
+/// Temporary files containing the text and the patterns, so that child
+/// processes measure exactly the same input as their parent.
+pub struct IsolationFiles {
+    text_file: NamedTempFile,
+    patterns_file: NamedTempFile,
+}
+
+impl IsolationFiles {
+    /// Writes the text and the patterns to new temporary files which are
+    /// deleted as soon as this object is dropped.
+    pub fn new(text: &[u8], patterns: &[Vec<u8>]) -> io::Result<Self> {
+        let mut text_file = NamedTempFile::new()?;
+        text_file.write_all(text)?;
+
+        let mut patterns_file = NamedTempFile::new()?;
+        serde_json::to_writer(&mut patterns_file, patterns)?;
+
+        Ok(Self {
+            text_file,
+            patterns_file,
+        })
+    }
+}
+
+/// Measures a cell in a child process running the same executable with the
+/// same CLI arguments.
+///
+/// The child process sends its measurements line by line, so that a crash
+/// of the child, e. g. because of a panic or the kernel running out of
+/// memory, doesn't affect the parent. The child is killed if a single
+/// execution or the whole cell takes longer than the timeouts given in the
+/// CLI parameters, or once the `deadline` of the total budget has passed,
+/// skipping the cell. In all cases, the measurements are discarded and the
+/// outcome's status tells why the child has failed.
+pub fn measure_isolated_cell(
+    isolation_files: &IsolationFiles,
+    algorithm: &str,
+    pattern_index: Option<usize>,
+    cli_params: &CLIParams,
+    deadline: Option<Instant>,
+) -> CellOutcome {
+    match run_child(
+        isolation_files,
+        algorithm,
+        pattern_index,
+        cli_params,
+        deadline,
+    ) {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            eprintln!("Error while running isolated measurement: {}", err);
+
+            CellOutcome {
+                constructions: Vec::new(),
+                measurements: Vec::new(),
+                status: Status::Crash,
+            }
+        }
+    }
+}
+
+/// Spawns the child process measuring a cell and collects its messages
+/// until the child exits, times out or the `deadline` has passed.
+fn run_child(
+    isolation_files: &IsolationFiles,
+    algorithm: &str,
+    pattern_index: Option<usize>,
+    cli_params: &CLIParams,
+    deadline: Option<Instant>,
+) -> io::Result<CellOutcome> {
+    let start = Instant::now();
+    let pattern_index = pattern_index.map_or(String::from("all"), |x| x.to_string());
+
+    let mut child = Command::new(env::current_exe()?)
+        .args(env::args_os().skip(1))
+        .arg("--isolatedcell")
+        .arg(algorithm)
+        .arg(pattern_index)
+        .arg(isolation_files.text_file.path())
+        .arg(isolation_files.patterns_file.path())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    // Read the messages in a separate thread, so that waiting for them can
+    // time out. Unwrapping is safe here as stdout has been piped above.
+    let stdout = child.stdout.take().unwrap();
+    let (sender, receiver) = mpsc::channel();
+
+    let reader = thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+
+            // The last line might be incomplete if the child has crashed, so
+            // lines that can't be parsed are ignored
+            if let Ok(message) = serde_json::from_str::<CellMessage>(&line) {
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let cell_deadline = cli_params
+        .cell_timeout
+        .map(|x| start + Duration::from_secs_f64(x));
+
+    let mut constructions = Vec::new();
+    let mut measurements = Vec::new();
+    // The status of a child that has been killed
+    let mut stopped = None;
+
+    loop {
+        // Every message marks the end of an execution or index construction,
+        // so the next one has to arrive within the execution timeout
+        let execution_deadline = cli_params
+            .execution_timeout
+            .map(|x| Instant::now() + Duration::from_secs_f64(x));
+
+        let message = match earliest(earliest(execution_deadline, cell_deadline), deadline) {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match message {
+            Ok(CellMessage::Construction(name, phase)) => constructions.push((name, phase)),
+            Ok(CellMessage::WarmUp) => (),
+            Ok(CellMessage::Execution(measurement)) => measurements.push(measurement),
+            Err(RecvTimeoutError::Timeout) => {
+                stopped = Some(if deadline.is_some_and(|x| Instant::now() >= x) {
+                    Status::Skipped
+                } else {
+                    Status::Timeout
+                });
+
+                // The child might have exited just now, which is fine
+                let _ = child.kill();
+
+                break;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    let exit = child.wait()?;
+
+    // The reader stops as soon as the child's stdout has been closed
+    let _ = reader.join();
+
+    let status = match stopped {
+        Some(status) => status,
+        None => exit_status(exit),
+    };
+
+    if status != Status::Ok {
+        measurements.clear();
+    }
+
+    Ok(CellOutcome {
+        constructions,
+        measurements,
+        status,
+    })
+}
+
+/// Returns the earlier one of two optional deadlines.
+fn earliest(a: Option<Instant>, b: Option<Instant>) -> Option<Instant> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Returns the status of a measurement given the exit status of the child
+/// process running it.
+fn exit_status(exit_status: ExitStatus) -> Status {
+    if exit_status.success() {
+        return Status::Ok;
+    }
+
+    match (exit_status.code(), signal(exit_status)) {
+        (Some(PANIC_EXIT_CODE), _) | (_, Some(SIGABRT)) => Status::Panic,
+        (_, Some(SIGKILL)) => Status::Killed,
+        _ => Status::Crash,
+    }
+}
+
+/// Returns the signal which has terminated a process, `None` if it has
+/// exited normally.
+#[cfg(unix)]
+fn signal(exit_status: ExitStatus) -> Option<i32> {
+    exit_status.signal()
+}
+
+/// Processes aren't terminated by signals on other platforms, so their
+/// status is given by the exit code only.
+#[cfg(not(unix))]
+fn signal(_exit_status: ExitStatus) -> Option<i32> {
+    None
+}
+
+/// Measures a single cell in a child process, reading the text and the
+/// patterns from the files created by the parent process.
+///
+/// It writes the measurements to standard output, one message per line.
+pub fn run_isolated_cell(
+    cell: &IsolatedCell,
+    cli_params: &CLIParams,
+) -> Result<(), Box<dyn Error>> {
+    let text = fs::read(&cell.text_file)?;
+    let patterns: Vec<Vec<u8>> = serde_json::from_reader(File::open(&cell.patterns_file)?)?;
+
+    let algorithm_fn = match_algorithm(&cell.algorithm).ok_or("Unknown algorithm given.")?;
+
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    let mut result = Ok(());
+
+    // Each message is sent as soon as possible, so that the parent can tell
+    // how far the measurement has come if it crashes or times out
+    measure_cell(
+        &algorithm_fn,
+        &text,
+        &patterns,
+        cell.pattern_index,
+        &mut IndexCache::default(),
+        cli_params,
+        // The parent kills the child once the total budget has been used up
+        None,
+        &mut |event| {
+            if result.is_ok() {
+                result = write_message(&mut writer, &CellMessage::from(event));
+            }
+        },
+    );
+
+    result
+}
+
+/// Writes a single message as a line of JSON.
+fn write_message<W: Write>(writer: &mut W, message: &CellMessage) -> Result<(), Box<dyn Error>> {
+    serde_json::to_writer(&mut *writer, message)?;
+    writeln!(writer)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_earliest() {
+        let now = Instant::now();
+        let later = now + Duration::from_secs(1);
+
+        assert_eq!(earliest(Some(now), Some(later)), Some(now));
+        assert_eq!(earliest(None, Some(later)), Some(later));
+        assert_eq!(earliest(None, None), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_exit_status() {
+        assert_eq!(exit_status(ExitStatus::from_raw(0)), Status::Ok);
+        assert_eq!(exit_status(ExitStatus::from_raw(101 << 8)), Status::Panic);
+        assert_eq!(exit_status(ExitStatus::from_raw(SIGABRT)), Status::Panic);
+        assert_eq!(exit_status(ExitStatus::from_raw(SIGKILL)), Status::Killed);
+        assert_eq!(exit_status(ExitStatus::from_raw(11)), Status::Crash);
+        assert_eq!(exit_status(ExitStatus::from_raw(1 << 8)), Status::Crash);
+    }
+}
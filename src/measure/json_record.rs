@@ -0,0 +1,246 @@
+use serde::Serialize;
+
+use crate::algorithms::occurrences::QueryMode;
+use crate::cli::CLIParams;
+use crate::match_algorithm::{match_algorithm, TypedAlgorithm};
+use crate::measure::cache::CacheMode;
+use crate::measure::counters::OperationCounts;
+use crate::measure::measurement_result::Status;
+use crate::measure::memory::MemoryUsage;
+use crate::measure::statistics::Summary;
+use crate::measure::throughput::Throughput;
+use crate::pattern::PatternSource;
+use crate::text::TextSource;
+
+/// The algorithm, its inputs and the settings a measurement has been taken
+/// with, shared by all JSON records of the measurement.
+#[derive(Serialize)]
+pub struct JSONContext<'a> {
+    algorithm: AlgorithmContext<'a>,
+    text: InputContext<'a, TextSource>,
+    pattern: InputContext<'a, PatternSource>,
+
+    status: Status,
+    cpu: Option<usize>,
+    threads: usize,
+    query: QueryMode,
+    cache: CacheMode,
+}
+
+#[derive(Serialize)]
+struct AlgorithmContext<'a> {
+    name: &'a str,
+    /// The name of the algorithm as given by the `--algorithms` argument.
+    argument: &'a str,
+    parameters: AlgorithmParameters<'a>,
+}
+
+/// The CLI parameters affecting an algorithm, only set if the algorithm
+/// depends on them.
+#[derive(Default, Serialize)]
+struct AlgorithmParameters<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suffix_array_algorithm: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    occ_block_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maximum_error: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct InputContext<'a, S> {
+    length: usize,
+    source: &'a S,
+}
+
+impl<'a> JSONContext<'a> {
+    /// Creates the context of a measurement of the algorithm given by its
+    /// CLI parameter name `argument` and display `name`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: &'a str,
+        argument: &'a str,
+        text_length: usize,
+        pattern_length: usize,
+        status: Status,
+        cpu: Option<usize>,
+        threads: usize,
+        query_mode: QueryMode,
+        cache_mode: CacheMode,
+        cli_params: &'a CLIParams,
+    ) -> Self {
+        let suffix_array_algorithm = Some(cli_params.suffix_array_algorithm.as_str());
+
+        let parameters = match match_algorithm(argument) {
+            Some(TypedAlgorithm::SuffixArrayAlgorithm(_)) => AlgorithmParameters {
+                suffix_array_algorithm,
+                ..AlgorithmParameters::default()
+            },
+            Some(TypedAlgorithm::BWTAlgorithm(_)) => AlgorithmParameters {
+                suffix_array_algorithm,
+                occ_block_size: Some(cli_params.occ_block_size),
+                ..AlgorithmParameters::default()
+            },
+            Some(TypedAlgorithm::ApproximativeAlgorithm(_)) => AlgorithmParameters {
+                maximum_error: cli_params.maximum_error,
+                ..AlgorithmParameters::default()
+            },
+            _ => AlgorithmParameters::default(),
+        };
+
+        Self {
+            algorithm: AlgorithmContext {
+                name,
+                argument,
+                parameters,
+            },
+            text: InputContext {
+                length: text_length,
+                source: &cli_params.text_source,
+            },
+            pattern: InputContext {
+                length: pattern_length,
+                source: &cli_params.pattern_source,
+            },
+
+            status,
+            cpu,
+            threads,
+            query: query_mode,
+            cache: cache_mode,
+        }
+    }
+}
+
+/// A record of a single execution of an algorithm, or of an algorithm that
+/// couldn't be measured, with nested phase timings.
+#[derive(Serialize)]
+pub struct JSONRecord<'a> {
+    #[serde(flatten)]
+    context: JSONContext<'a>,
+
+    execution: Option<usize>,
+    executions: usize,
+    matches: Option<usize>,
+    outlier: Option<bool>,
+
+    preparation: Option<PhaseRecord>,
+    search: Option<SearchRecord>,
+}
+
+/// The measurements of the preparation phase of an execution.
+#[derive(Serialize)]
+pub struct PhaseRecord {
+    pub time_ns: u128,
+    pub memory: Option<MemoryUsage>,
+}
+
+/// The measurements of the search phase of an execution.
+#[derive(Serialize)]
+pub struct SearchRecord {
+    pub time_ns: u128,
+    pub memory: Option<MemoryUsage>,
+    pub counts: Option<OperationCounts>,
+    pub mean_shift: Option<f64>,
+    pub throughput: Throughput,
+}
+
+impl<'a> JSONRecord<'a> {
+    pub fn new(
+        context: JSONContext<'a>,
+        execution: usize,
+        executions: usize,
+        matches: usize,
+        outlier: bool,
+        preparation: Option<PhaseRecord>,
+        search: SearchRecord,
+    ) -> Self {
+        Self {
+            context,
+
+            execution: Some(execution),
+            executions,
+            matches: Some(matches),
+            outlier: Some(outlier),
+
+            preparation,
+            search: Some(search),
+        }
+    }
+
+    /// Creates a record of an algorithm that couldn't be measured, leaving
+    /// all measured values empty.
+    pub fn failed(context: JSONContext<'a>) -> Self {
+        Self {
+            context,
+
+            execution: None,
+            executions: 0,
+            matches: None,
+            outlier: None,
+
+            preparation: None,
+            search: None,
+        }
+    }
+}
+
+/// A record containing the summary statistics of all executions of an
+/// algorithm with one pattern, nested by phase.
+///
+/// The preparation is `None` for algorithms without a preparation phase and
+/// both phases are `None` if the algorithm couldn't be measured.
+#[derive(Serialize)]
+pub struct SummaryJSONRecord<'a> {
+    #[serde(flatten)]
+    context: JSONContext<'a>,
+
+    executions: usize,
+    outliers: usize,
+    matches: Option<usize>,
+
+    preparation: Option<PhaseSummaryRecord<'a>>,
+    search: Option<SearchSummaryRecord<'a>>,
+}
+
+/// The summary statistics of the preparation phase in nanoseconds and the
+/// maximum memory usage of all executions.
+#[derive(Serialize)]
+pub struct PhaseSummaryRecord<'a> {
+    pub time_ns: &'a Summary,
+    pub memory: Option<MemoryUsage>,
+}
+
+/// The summary statistics of the search phase in nanoseconds, the maximum
+/// memory usage of all executions and the metrics derived from the mean
+/// runtime.
+#[derive(Serialize)]
+pub struct SearchSummaryRecord<'a> {
+    pub time_ns: &'a Summary,
+    pub memory: Option<MemoryUsage>,
+    pub counts: Option<OperationCounts>,
+    pub mean_shift: Option<f64>,
+    pub throughput: Throughput,
+}
+
+impl<'a> SummaryJSONRecord<'a> {
+    pub fn new(
+        context: JSONContext<'a>,
+        executions: usize,
+        outliers: usize,
+        matches: Option<usize>,
+        preparation: Option<PhaseSummaryRecord<'a>>,
+        search: Option<SearchSummaryRecord<'a>>,
+    ) -> Self {
+        Self {
+            context,
+
+            executions,
+            outliers,
+            matches,
+
+            preparation,
+            search,
+        }
+    }
+}
@@ -1,15 +1,61 @@
-use std::time::Duration;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::hint::black_box;
+use std::mem;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::cli::CLIParams;
-use crate::match_algorithm::TypedAlgorithm;
-use crate::measure::measurement_result::MeasurementResult;
-use crate::measure::{Measure, MultiplePatternMeasure};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
 
-/// A single measurement containing an optional preparation runtime,
-/// a mandatory execution runtime (of the actual pattern matching algorithm
+use crate::cli::{CLIParams, Executions};
+use crate::match_algorithm::{algorithm_name, TypedAlgorithm};
+use crate::measure::budget::Budget;
+use crate::measure::cache::{evict_caches, CacheMode};
+use crate::measure::chart::{read_samples, write_charts};
+use crate::measure::checkpoint::{open_checkpoint, Configuration, ResumedResults};
+use crate::measure::counters::OperationCounts;
+use crate::measure::index_cache::IndexCache;
+use crate::measure::isolation::{measure_isolated_cell, IsolationFiles};
+use crate::measure::measurement_result::{MeasurementResult, Status};
+use crate::measure::memory::{measure_memory, MemoryUsage};
+use crate::measure::metadata::Metadata;
+use crate::measure::output::{append_writer, open_writers, OutputFormat, RecordWriter, Sink};
+use crate::measure::progress::Progress;
+use crate::measure::report::{format_report, ReportEntry};
+use crate::measure::scheduler::SchedulerSettings;
+use crate::measure::statistics::{durations_as_nanos, relative_confidence_interval_width};
+use crate::measure::{timer, IndexMeasure, Measure, MultiplePatternMeasure};
+
+/// A single measurement containing an optional preparation phase,
+/// a mandatory execution phase (of the actual pattern matching algorithm
 /// itself) and the number of matches, i. e. how often the pattern has been
 /// found in the text.
-pub type SingleMeasurement = (Option<Duration>, Duration, usize);
+///
+/// If the program has been built with the `counters` feature, single pattern
+/// algorithms also count the operations of their execution phase.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct SingleMeasurement {
+    pub preparation: Option<PhaseMeasurement>,
+    pub algorithm: PhaseMeasurement,
+    pub matches: usize,
+    pub counts: Option<OperationCounts>,
+}
+
+/// The measurement of a single phase of an algorithm containing its runtime
+/// and, if memory tracking is enabled, its heap memory usage.
+#[derive(Clone, Copy, Deserialize, Serialize)]
+pub struct PhaseMeasurement {
+    pub duration: Duration,
+    pub memory: Option<MemoryUsage>,
+}
+
+/// The minimum number of executions when the number of executions is
+/// chosen adaptively.
+const MIN_ADAPTIVE_EXECUTIONS: usize = 5;
 
 pub struct Measurement {
     algorithms: Vec<(String, TypedAlgorithm)>,
@@ -35,122 +81,1417 @@ impl Measurement {
         }
     }
 
-    pub fn run_measurement(&mut self) -> &mut Self {
+    /// Measures all algorithms with all patterns.
+    ///
+    /// If the algorithm only takes a single pattern, it is measured once for
+    /// every given pattern. If the algorithm takes multiple patterns, it is
+    /// measured once with all given patterns. If isolation is enabled, each
+    /// of these cells is measured in a separate child process.
+    ///
+    /// If shuffling is enabled, the cells are measured in a random order
+    /// seeded by the `--seed` argument or the seed drawn instead. Without isolation and parallel
+    /// threads, the executions of all cells are interleaved, so that a drift
+    /// of the system's performance during the measurement affects all cells
+    /// alike. The results are grouped by cell in the original order
+    /// nevertheless.
+    ///
+    /// The results of each cell are printed as CSV as soon as the cell and
+    /// all cells before it have been measured. If a checkpoint file is given,
+    /// the configuration of the measurement is stored in it. When resuming,
+    /// the cells whose results have been written to the results file already
+    /// are not measured again and the results of the other cells are
+    /// appended to the file. The summary and the charts only cover the cells
+    /// measured after resuming.
+    ///
+    /// Once an algorithm has timed out, it is skipped for all longer patterns.
+    /// Once an algorithm has used up the time budget given by
+    /// `--algorithmbudget` or the whole measurement has used up the time
+    /// budget given by `--budget`, the remaining cells are skipped. Skipped
+    /// cells and all cells after them are measured again when resuming, e. g.
+    /// with a larger budget.
+    ///
+    /// It returns an error if the scheduler settings can't be applied, the
+    /// checkpoint or results file can't be resumed, the isolated measurements
+    /// can't be prepared or the results can't be written.
+    pub fn run_measurement(&mut self) -> Result<&mut Self, String> {
+        // Pin the thread and raise its priority until all measurements have
+        // been taken, child processes inherit these settings
+        let _scheduler_settings =
+            SchedulerSettings::apply(&self.cli_params).map_err(|err| err.to_string())?;
+
+        let cells = self.cells();
+
+        let resumed_results = match &self.cli_params.checkpoint_file {
+            Some(file_name) => self.resumed_results(file_name, &cells)?,
+            None => ResumedResults::default(),
+        };
+
+        // The statuses of the cells measured before resuming the measurement
+        let resumed_statuses: Vec<Option<Status>> = (0..cells.len())
+            .map(|i| resumed_results.statuses.get(i).copied())
+            .collect();
+
+        let progress = Progress::new(
+            cells.len() - resumed_results.statuses.len(),
+            self.cli_params.progress,
+        );
+        let budget = Budget::new(&self.cli_params);
+
+        // Without any output files, the results are written to stdout
+        let output_files = if self.cli_params.output_files.is_empty() {
+            vec![String::from("-")]
+        } else {
+            self.cli_params.output_files.clone()
+        };
+
+        // A checkpoint requires a single results file, which is continued
+        // instead of overwritten when resuming
+        let writers = if self.cli_params.resume {
+            vec![append_writer(
+                &output_files[0],
+                self.results_format(&output_files[0]),
+                resumed_results.length,
+                !self.cli_params.no_header,
+            )?]
+        } else {
+            self.open_writers(&output_files)?
+        };
+
+        let output = Mutex::new(CellOutput {
+            outcomes: vec![None; cells.len()],
+            printed: resumed_results.statuses.len(),
+            writers,
+            reported_constructions: resumed_results.constructions,
+            measurement_results: Vec::new(),
+            error: None,
+        });
+
+        let on_outcome = |cell_index: usize, outcome: CellOutcome| {
+            // The mutex can only be poisoned if a worker has panicked, in
+            // which case the panic is propagated anyway, so unwrapping its
+            // lock is safe here
+            let output = &mut *output.lock().unwrap();
+
+            output.outcomes[cell_index] = Some(outcome);
+
+            self.print_results(output, &cells);
+        };
+
+        // Interleaving executions only works for cells measured one after
+        // another in this process
+        if self.cli_params.shuffle && !self.cli_params.isolate && self.cli_params.threads == 1 {
+            self.measure_interleaved_cells(
+                &cells,
+                &resumed_statuses,
+                &progress,
+                &budget,
+                &on_outcome,
+            );
+        } else {
+            self.measure_queued_cells(&cells, &resumed_statuses, &progress, &budget, &on_outcome)?;
+        }
+
+        progress.finish();
+
+        let mut output = output.into_inner().unwrap();
+
+        let report = self.report(&output.measurement_results, &output.writers);
+
+        for writer in output.writers.iter_mut() {
+            if let Err(err) = writer.write_table(&report).and_then(|_| writer.finish()) {
+                output.error.get_or_insert(err);
+            }
+        }
+
+        self.measurement_results = output.measurement_results;
+
+        match output.error {
+            Some(err) => Err(err),
+            None => Ok(self),
+        }
+    }
+
+    /// Writes the results of the measured cells to all outputs, as long as
+    /// all cells before them in the original order have been written
+    /// already. With `--aggregate`, a single record of
+    /// summary statistics is printed per result instead of one record per
+    /// execution.
+    ///
+    /// The written results are moved to the `measurement_results` of the
+    /// output, the first error while writing is stored in it, too.
+    fn print_results(&self, output: &mut CellOutput, cells: &[Cell]) {
+        while let Some(Some(_)) = output.outcomes.get(output.printed) {
+            // Unwrapping is safe here as the outcome has been checked above
+            let outcome = output.outcomes[output.printed].take().unwrap();
+
+            for measurement_result in self.cell_results(
+                &cells[output.printed],
+                outcome,
+                &mut output.reported_constructions,
+            ) {
+                for writer in output.writers.iter_mut() {
+                    let written = if self.cli_params.aggregate {
+                        measurement_result.write_summary_record(writer, &self.cli_params)
+                    } else {
+                        measurement_result.write_records(writer, &self.cli_params)
+                    };
+
+                    if let Err(err) = written {
+                        output.error.get_or_insert(err);
+                    }
+                }
+
+                output.measurement_results.push(measurement_result);
+            }
+
+            output.printed += 1;
+        }
+    }
+
+    /// Returns the results of a cell, preceded by the index constructions it
+    /// required unless they have been reported for another cell already.
+    fn cell_results(
+        &self,
+        cell: &Cell,
+        outcome: CellOutcome,
+        reported_constructions: &mut HashSet<String>,
+    ) -> Vec<MeasurementResult> {
         let mut measurement_results = Vec::new();
 
-        for (algorithm, algorithm_fn) in self.algorithms.iter() {
-            // If the algorithm only takes a single pattern, run it once for
-            // every given pattern. If the algorithm takes multiple patterns,
-            // run the algorithm once with all given patterns.
+        let (algorithm, algorithm_fn) = &self.algorithms[cell.algorithm_index];
+        let pattern_length = self.pattern_length(cell);
+
+        // Index constructions are reported only once, even if the index has
+        // been built by multiple child processes
+        for (construction, phase) in outcome.constructions {
+            if !reported_constructions.insert(construction.clone()) {
+                continue;
+            }
+
+            let measurement = SingleMeasurement {
+                preparation: Some(phase),
+                algorithm: PhaseMeasurement {
+                    duration: Duration::new(0, 0),
+                    memory: None,
+                },
+                matches: 0,
+                counts: None,
+            };
+
+            measurement_results.push(measurement_result(
+                &construction,
+                self.text.len(),
+                0,
+                vec![measurement],
+                false,
+                &self.cli_params,
+            ));
+        }
+
+        if outcome.status == Status::Ok {
+            let index_query = matches!(
+                algorithm_fn,
+                TypedAlgorithm::SuffixArrayAlgorithm(_) | TypedAlgorithm::BWTAlgorithm(_)
+            );
+
+            measurement_results.push(measurement_result(
+                algorithm,
+                self.text.len(),
+                pattern_length,
+                outcome.measurements,
+                index_query,
+                &self.cli_params,
+            ));
+        } else {
+            measurement_results.push(MeasurementResult::failed(
+                algorithm,
+                self.text.len(),
+                pattern_length,
+                outcome.status,
+                self.cli_params.pinned_cpu,
+                self.cli_params.threads,
+                // Unwrapping is safe here because of the checks done in cli_params.valid()
+                self.cli_params.query_mode.unwrap(),
+                self.cli_params.cache_mode.unwrap(),
+            ));
+        }
+
+        measurement_results
+    }
+
+    /// Returns all cells to be measured, grouped by algorithm and ordered by
+    /// pattern.
+    fn cells(&self) -> Vec<Cell> {
+        let mut cells = Vec::new();
+
+        for (algorithm_index, (_, algorithm_fn)) in self.algorithms.iter().enumerate() {
             match algorithm_fn {
-                TypedAlgorithm::SinglePatternAlgorithm(_)
-                | TypedAlgorithm::SuffixArrayAlgorithm(_)
-                | TypedAlgorithm::BWTAlgorithm(_)
-                | TypedAlgorithm::ApproximativeAlgorithm(_) => {
-                    for pattern in self.patterns.iter() {
-                        let measurements =
-                            measure_exeuctions(pattern, &self.text, algorithm_fn, &self.cli_params);
-
-                        let preparation_durations = measurements.iter().map(|x| x.0).collect();
-                        let algorithm_durations = measurements.iter().map(|x| x.1).collect();
-                        let matches = measurements.get(0).unwrap().2;
-
-                        measurement_results.push(MeasurementResult::new(
-                            algorithm,
-                            self.text.len(),
-                            pattern.len(),
-                            matches,
-                            preparation_durations,
-                            algorithm_durations,
-                        ));
+                TypedAlgorithm::MultiplePatternAlgorithm(_) => cells.push(Cell {
+                    algorithm_index,
+                    pattern_index: None,
+                }),
+                _ => cells.extend((0..self.patterns.len()).map(|i| Cell {
+                    algorithm_index,
+                    pattern_index: Some(i),
+                })),
+            }
+        }
+
+        cells
+    }
+
+    /// Returns the length of the pattern a cell is measured with.
+    fn pattern_length(&self, cell: &Cell) -> usize {
+        // TODO does 0 make sense when benchmarking multiple pattern at once?
+        // Or rather use -1 or something like that?
+        cell.pattern_index.map_or(0, |i| self.patterns[i].len())
+    }
+
+    /// Returns a description of a cell for progress reports.
+    fn describe(&self, cell: &Cell) -> String {
+        let algorithm = algorithm_name(&self.algorithms[cell.algorithm_index].0);
+
+        match cell.pattern_index {
+            Some(i) => format!(
+                "{} with pattern {} of {} (length {})",
+                algorithm,
+                i + 1,
+                self.patterns.len(),
+                self.patterns[i].len()
+            ),
+            None => format!("{} with all {} patterns", algorithm, self.patterns.len()),
+        }
+    }
+
+    /// Measures the cells one after another, in a random order if shuffling
+    /// is enabled and in separate child processes if isolation is enabled.
+    ///
+    /// With multiple threads, each worker thread measures the next cell that
+    /// hasn't been taken by another worker yet, using its own copy of the
+    /// text and the patterns.
+    ///
+    /// Once an algorithm has timed out, it is skipped for all longer patterns
+    /// that haven't been measured yet. Once `budget` is exhausted for an
+    /// algorithm, it is skipped for all cells that haven't been measured yet.
+    /// Cells that are being measured when the total budget is used up are
+    /// stopped and skipped, too.
+    ///
+    /// Cells with a `resumed_status` have been measured before resuming the
+    /// measurement and are left out. Each cell is reported to `progress`
+    /// before it is measured and its outcome is passed to `on_outcome` with
+    /// the index of the cell afterwards.
+    ///
+    /// It returns an error if the files for the child processes can't be
+    /// created.
+    fn measure_queued_cells(
+        &self,
+        cells: &[Cell],
+        resumed_statuses: &[Option<Status>],
+        progress: &Progress,
+        budget: &Budget,
+        on_outcome: &(dyn Fn(usize, CellOutcome) + Sync),
+    ) -> Result<(), String> {
+        let isolation_files = if self.cli_params.isolate {
+            Some(IsolationFiles::new(&self.text, &self.patterns).map_err(|err| err.to_string())?)
+        } else {
+            None
+        };
+
+        let mut order: Vec<usize> = (0..cells.len())
+            .filter(|&i| resumed_statuses[i].is_none())
+            .collect();
+
+        if self.cli_params.shuffle {
+            order.shuffle(&mut self.rng());
+        }
+
+        // The shortest pattern length each algorithm has timed out with,
+        // including the timeouts before resuming the measurement
+        let mut timed_out_pattern_lengths: HashMap<usize, usize> = HashMap::new();
+
+        for (cell, status) in cells.iter().zip(resumed_statuses) {
+            if *status == Some(Status::Timeout) {
+                let pattern_length = self.pattern_length(cell);
+                let timed_out_pattern_length = timed_out_pattern_lengths
+                    .entry(cell.algorithm_index)
+                    .or_insert(pattern_length);
+
+                *timed_out_pattern_length = (*timed_out_pattern_length).min(pattern_length);
+            }
+        }
+
+        let queue = Mutex::new(order.into_iter());
+        let timed_out_pattern_lengths = Mutex::new(timed_out_pattern_lengths);
+
+        // The mutexes can only be poisoned if a worker has panicked, in which
+        // case the panic is propagated anyway, so unwrapping their locks is
+        // safe here
+        let worker = || {
+            let (text, patterns): (Cow<[u8]>, Cow<[Vec<u8>]>) = if self.cli_params.threads > 1 {
+                (
+                    Cow::Owned(self.text.clone()),
+                    Cow::Owned(self.patterns.clone()),
+                )
+            } else {
+                (Cow::Borrowed(&self.text), Cow::Borrowed(&self.patterns))
+            };
+            let mut index_cache = IndexCache::default();
+
+            loop {
+                // Take the next cell in a separate statement, so that the
+                // queue isn't locked while measuring it
+                let cell_index = queue.lock().unwrap().next();
+
+                let cell = match cell_index {
+                    Some(cell_index) => &cells[cell_index],
+                    None => break,
+                };
+                let (algorithm, algorithm_fn) = &self.algorithms[cell.algorithm_index];
+                let pattern_length = self.pattern_length(cell);
+
+                let skipped = timed_out_pattern_lengths
+                    .lock()
+                    .unwrap()
+                    .get(&cell.algorithm_index)
+                    .is_some_and(|&x| pattern_length > x)
+                    || budget.exhausted(cell.algorithm_index);
+
+                progress.report(
+                    if skipped { "Skipping" } else { "Measuring" },
+                    &self.describe(cell),
+                );
+
+                let start = Instant::now();
+
+                let outcome = if skipped {
+                    CellOutcome {
+                        constructions: Vec::new(),
+                        measurements: Vec::new(),
+                        status: Status::Skipped,
                     }
-                }
-                TypedAlgorithm::MultiplePatternAlgorithm(f) => {
-                    // Run given multi pattern algorithm for given number of
-                    // executions
-                    let measurements = (0..self.cli_params.executions)
-                        .map(|_| f.measure(&self.patterns, &self.text, &self.cli_params))
-                        .collect::<Vec<_>>();
-
-                    let preparation_durations = measurements.iter().map(|x| x.0).collect();
-                    let algorithm_durations = measurements.iter().map(|x| x.1).collect();
-                    let matches = measurements.get(0).unwrap().2;
-
-                    measurement_results.push(MeasurementResult::new(
+                } else if let Some(isolation_files) = &isolation_files {
+                    measure_isolated_cell(
+                        isolation_files,
                         algorithm,
-                        self.text.len(),
-                        // TODO does 0 make sense when benchmarking multiple pattern at once?
-                        // Or rather use -1 or something like that?
-                        0,
-                        matches,
-                        preparation_durations,
-                        algorithm_durations,
-                    ));
+                        cell.pattern_index,
+                        &self.cli_params,
+                        budget.deadline(),
+                    )
+                } else {
+                    measure_cell(
+                        algorithm_fn,
+                        &text,
+                        &patterns,
+                        cell.pattern_index,
+                        &mut index_cache,
+                        &self.cli_params,
+                        budget.deadline(),
+                        &mut |_| (),
+                    )
+                };
+
+                budget.charge(cell.algorithm_index, start.elapsed());
+
+                if outcome.status == Status::Timeout {
+                    let mut timed_out_pattern_lengths = timed_out_pattern_lengths.lock().unwrap();
+                    let timed_out_pattern_length = timed_out_pattern_lengths
+                        .entry(cell.algorithm_index)
+                        .or_insert(pattern_length);
+
+                    *timed_out_pattern_length = (*timed_out_pattern_length).min(pattern_length);
                 }
+
+                // Unwrapping is safe here as the cell has been taken from the
+                // queue above
+                on_outcome(cell_index.unwrap(), outcome);
+
+                progress.complete_cell();
             }
+        };
+
+        if self.cli_params.threads > 1 {
+            thread::scope(|scope| {
+                for _ in 0..self.cli_params.threads {
+                    scope.spawn(worker);
+                }
+            });
+        } else {
+            worker();
         }
 
-        self.measurement_results = measurement_results;
+        Ok(())
+    }
+
+    /// Measures all cells in rounds, each round executing every unfinished
+    /// cell once in a new random order.
+    ///
+    /// The warm-up executions of a cell are run right before its first
+    /// measured execution. Cells with a `resumed_status` have been measured
+    /// before resuming the measurement and are left out. Once `budget` is
+    /// exhausted for an algorithm, its unfinished cells are skipped,
+    /// discarding the executions measured so far. Each round and each cell
+    /// that has been measured completely or skipped are reported to
+    /// `progress`, the outcome of the cell is passed to `on_outcome` with its
+    /// index.
+    fn measure_interleaved_cells(
+        &self,
+        cells: &[Cell],
+        resumed_statuses: &[Option<Status>],
+        progress: &Progress,
+        budget: &Budget,
+        on_outcome: &dyn Fn(usize, CellOutcome),
+    ) {
+        let mut rng = self.rng();
+        let mut index_cache = IndexCache::default();
+        let mut progresses: Vec<CellProgress> =
+            cells.iter().map(|_| CellProgress::default()).collect();
+        let mut unfinished: Vec<usize> = (0..cells.len())
+            .filter(|&i| resumed_statuses[i].is_none())
+            .collect();
+
+        // Build all indices up front in the original order, so that each
+        // construction is reported with the first cell requiring it
+        for &cell_index in unfinished.iter() {
+            progresses[cell_index].build_index(
+                &self.algorithms[cells[cell_index].algorithm_index].1,
+                &self.text,
+                &mut index_cache,
+                &self.cli_params,
+                &mut |_| (),
+            );
+        }
+
+        for round in 1.. {
+            unfinished.retain(|&i| {
+                let finished = progresses[i].finished(&self.cli_params);
+                let skipped = !finished && budget.exhausted(cells[i].algorithm_index);
+
+                if finished || skipped {
+                    let mut outcome = mem::take(&mut progresses[i]).into_outcome();
+
+                    if skipped {
+                        outcome.measurements.clear();
+                        outcome.status = Status::Skipped;
+                    }
+
+                    on_outcome(i, outcome);
+
+                    progress.complete_cell();
+                    progress.report(
+                        if skipped { "Skipped" } else { "Measured" },
+                        &self.describe(&cells[i]),
+                    );
+                }
+
+                !(finished || skipped)
+            });
+
+            if unfinished.is_empty() {
+                break;
+            }
+
+            progress.report(
+                "Measuring",
+                &format!(
+                    "round {} of the {} unfinished cells in random order",
+                    round,
+                    unfinished.len()
+                ),
+            );
+
+            unfinished.shuffle(&mut rng);
+
+            for &cell_index in unfinished.iter() {
+                let cell = &cells[cell_index];
+                let start = Instant::now();
+
+                progresses[cell_index].measure_execution(
+                    &self.algorithms[cell.algorithm_index].1,
+                    &self.text,
+                    &self.patterns,
+                    cell.pattern_index,
+                    &mut index_cache,
+                    &self.cli_params,
+                    &mut |_| (),
+                );
+
+                budget.charge(cell.algorithm_index, start.elapsed());
+            }
+        }
+    }
 
-        self
+    /// Returns the random number generator determining the order of the
+    /// cells, seeded by the `--seed` argument or the seed drawn instead.
+    fn rng(&self) -> StdRng {
+        SeedableRng::seed_from_u64(self.cli_params.seed)
     }
 
-    pub fn print_csv(&self) -> Result<(), String> {
-        if !self.measurement_results.is_empty() {
-            // Set to no_header so that there will be no header printed if
-            // no_header is set, as the variable will already be true then
-            let mut csv_header_printed = self.cli_params.no_header;
+    /// Writes the summary statistics of all measurement results to the files
+    /// given by the `--summary` argument, if it has been set.
+    pub fn write_summary(&self) -> Result<(), String> {
+        let mut writers = self.open_writers(&self.cli_params.summary_files)?;
+        let report = self.report(&self.measurement_results, &writers);
 
+        for writer in writers.iter_mut() {
             for measurement_result in self.measurement_results.iter() {
-                // TODO is there a nicer way to do this?
-                match measurement_result.print_csv(!csv_header_printed) {
-                    Ok(_) => (),
-                    Err(error) => return Err(error.to_string()),
-                }
+                measurement_result.write_summary_record(writer, &self.cli_params)?;
+            }
+
+            writer.write_table(&report)?;
+            writer.finish()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the SVG charts of this run and of the raw results given by the
+    /// `--chartinput` arguments to the directory given by the `--charts`
+    /// argument, if it has been set.
+    pub fn write_charts(&self) -> Result<(), String> {
+        let directory = match &self.cli_params.chart_directory {
+            Some(directory) => directory,
+            None => return Ok(()),
+        };
+
+        let mut samples = Vec::new();
+
+        for file_name in self.cli_params.chart_inputs.iter() {
+            samples.extend(read_samples(file_name, self.cli_params.exclude_outliers)?);
+        }
+
+        for measurement_result in self.measurement_results.iter() {
+            samples.extend(measurement_result.chart_samples(self.cli_params.exclude_outliers));
+        }
+
+        // Unwrapping is safe here because of the checks done in cli_params.valid()
+        write_charts(directory, &samples, self.cli_params.chart_log_axes.unwrap())
+    }
+
+    /// Returns the table ranking the algorithms of the given results, an
+    /// empty string if none of the given writers writes a table.
+    fn report(
+        &self,
+        measurement_results: &[MeasurementResult],
+        writers: &[RecordWriter<Sink>],
+    ) -> String {
+        if writers.iter().all(|x| x.format() != OutputFormat::Table) {
+            return String::new();
+        }
+
+        let entries: Vec<ReportEntry> = measurement_results
+            .iter()
+            .filter_map(MeasurementResult::report_entry)
+            .collect();
+
+        format_report(&entries, self.cli_params.baseline.as_deref())
+    }
+
+    /// Writes the configuration of this measurement to the given checkpoint
+    /// file or, if the measurement is resumed, reads the cells measured
+    /// before from the results file.
+    fn resumed_results(
+        &self,
+        checkpoint_file: &str,
+        cells: &[Cell],
+    ) -> Result<ResumedResults, String> {
+        let configuration = Configuration::new(&self.text, &self.patterns, &self.cli_params);
+
+        if !open_checkpoint(checkpoint_file, self.cli_params.resume, &configuration)? {
+            return Ok(ResumedResults::default());
+        }
+
+        let cells: Vec<(&str, usize)> = cells
+            .iter()
+            .map(|cell| {
+                let algorithm = algorithm_name(&self.algorithms[cell.algorithm_index].0);
+
+                (algorithm, self.pattern_length(cell))
+            })
+            .collect();
+
+        // A checkpoint requires a single results file, which is checked by
+        // cli_params.valid()
+        let file_name = &self.cli_params.output_files[0];
+
+        ResumedResults::read(
+            file_name,
+            self.results_format(file_name),
+            self.text.len(),
+            &cells,
+        )
+    }
+
+    /// Returns the format of the given output file, inferred from its
+    /// extension or given by `--format`.
+    fn results_format(&self, file_name: &str) -> OutputFormat {
+        // Unwrapping is safe here because of the checks done in cli_params.valid()
+        OutputFormat::from_extension(file_name).unwrap_or(self.cli_params.output_format.unwrap())
+    }
+
+    /// Opens a record writer for each of the given output files, their
+    /// format inferred from their extension or given by `--format`.
+    fn open_writers(&self, file_names: &[String]) -> Result<Vec<RecordWriter<Sink>>, String> {
+        // Unwrapping is safe here because of the checks done in cli_params.valid()
+        open_writers(
+            file_names,
+            self.cli_params.output_format.unwrap(),
+            !self.cli_params.no_header,
+        )
+    }
+
+    /// Writes the metadata of this run as JSON to the file given by the
+    /// `--metadata` argument, if it has been set.
+    pub fn write_metadata(&self) -> Result<(), String> {
+        match &self.cli_params.metadata_file {
+            Some(file_name) => Metadata::collect(&self.cli_params).write(file_name),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A cell of the measurement, i. e. an algorithm with a single pattern or,
+/// for multiple pattern algorithms, with all patterns.
+struct Cell {
+    algorithm_index: usize,
+    /// The index of the pattern or `None` for multiple pattern algorithms.
+    pattern_index: Option<usize>,
+}
+
+/// The outcome of measuring a cell, i. e. an algorithm with a single pattern
+/// or, for multiple pattern algorithms, with all patterns.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct CellOutcome {
+    /// The names and measurements of the index constructions required by
+    /// the algorithm.
+    pub constructions: Vec<(String, PhaseMeasurement)>,
+    pub measurements: Vec<SingleMeasurement>,
+    pub status: Status,
+}
+
+/// The outcomes of the cells measured so far and the results printed from
+/// them, shared by all workers measuring cells.
+struct CellOutput {
+    /// The outcomes in the order of the cells that haven't been printed yet.
+    outcomes: Vec<Option<CellOutcome>>,
+    /// The number of cells whose results have been printed.
+    printed: usize,
+    writers: Vec<RecordWriter<Sink>>,
+    reported_constructions: HashSet<String>,
+    measurement_results: Vec<MeasurementResult>,
+    /// The first error while printing the results.
+    error: Option<String>,
+}
+
+/// An event while measuring a cell, reported as soon as it happens.
+pub enum CellEvent<'a> {
+    /// An index required by the algorithm has been built.
+    Construction(&'a str, &'a PhaseMeasurement),
+    /// A warm-up execution has finished.
+    WarmUp,
+    /// An execution has been measured.
+    Execution(&'a SingleMeasurement),
+}
+
+/// The measurements of a cell taken so far, so that the executions of
+/// multiple cells can be interleaved.
+#[derive(Default)]
+struct CellProgress {
+    constructions: Vec<(String, PhaseMeasurement)>,
+    measurements: Vec<SingleMeasurement>,
+    /// The time of the first measured execution, `None` if the warm-up
+    /// executions haven't been run yet.
+    start: Option<Instant>,
+}
+
+impl CellProgress {
+    /// Measures the next execution of the cell, running the warm-up
+    /// executions first if this is the first execution.
+    ///
+    /// Indices required by the algorithm are taken from the given
+    /// `index_cache`. Every event is passed to `on_event`.
+    #[allow(clippy::too_many_arguments)]
+    fn measure_execution(
+        &mut self,
+        algorithm_fn: &TypedAlgorithm,
+        text: &[u8],
+        patterns: &[Vec<u8>],
+        pattern_index: Option<usize>,
+        index_cache: &mut IndexCache,
+        cli_params: &CLIParams,
+        on_event: &mut dyn FnMut(CellEvent),
+    ) {
+        self.build_index(algorithm_fn, text, index_cache, cli_params, on_event);
+
+        // Indices are cached now, so they aren't built again here
+        let execute = |index_cache: &mut IndexCache| {
+            measure_execution(
+                algorithm_fn,
+                text,
+                patterns,
+                pattern_index,
+                index_cache,
+                cli_params,
+            )
+        };
+
+        if self.start.is_none() {
+            // Unwrapping is safe here because of the checks done in cli_params.valid()
+            for _ in 0..cli_params.warmup_executions.unwrap() {
+                execute(index_cache);
+
+                on_event(CellEvent::WarmUp);
+            }
+
+            self.start = Some(Instant::now());
+        }
+
+        let single_measurement = execute(index_cache);
+
+        on_event(CellEvent::Execution(&single_measurement));
+
+        self.measurements.push(single_measurement);
+    }
+
+    /// Builds the index required by the algorithm of the cell unless it has
+    /// been cached by another cell already.
+    ///
+    /// Every construction is recorded for this cell and passed to `on_event`.
+    fn build_index(
+        &mut self,
+        algorithm_fn: &TypedAlgorithm,
+        text: &[u8],
+        index_cache: &mut IndexCache,
+        cli_params: &CLIParams,
+        on_event: &mut dyn FnMut(CellEvent),
+    ) {
+        let with_bwt = match algorithm_fn {
+            TypedAlgorithm::SuffixArrayAlgorithm(_) => false,
+            TypedAlgorithm::BWTAlgorithm(_) => true,
+            _ => return,
+        };
 
-                if !csv_header_printed {
-                    csv_header_printed = true;
+        index_cache.get(text, cli_params, with_bwt);
+
+        for (construction, phase) in index_cache.take_constructions() {
+            on_event(CellEvent::Construction(&construction, &phase));
+
+            self.constructions.push((construction, phase));
+        }
+    }
+
+    /// Returns whether all executions of the cell have been measured.
+    ///
+    /// These are either the given number of executions or, if the number of
+    /// executions is chosen adaptively, as many executions as needed for the
+    /// confidence interval of the mean runtime to be narrow enough or for the
+    /// time limit to be reached.
+    fn finished(&self, cli_params: &CLIParams) -> bool {
+        match cli_params.executions {
+            Executions::Fixed(executions) => self.measurements.len() >= executions,
+            Executions::Adaptive => {
+                if self.measurements.len() < MIN_ADAPTIVE_EXECUTIONS {
+                    return false;
                 }
+
+                let time_limit = Duration::from_secs_f64(cli_params.cell_time_limit);
+
+                let algorithm_durations: Vec<Duration> = self
+                    .measurements
+                    .iter()
+                    .map(|x| x.algorithm.duration)
+                    .collect();
+                let confidence_interval_width =
+                    relative_confidence_interval_width(&durations_as_nanos(&algorithm_durations))
+                        .unwrap_or(0f64);
+
+                confidence_interval_width <= cli_params.confidence_interval_width
+                    || self.start.is_some_and(|x| x.elapsed() >= time_limit)
             }
+        }
+    }
 
-            Ok(())
-        } else {
-            Err(String::from(
-                "Can't print CSV records before measurements have been taken",
-            ))
+    fn into_outcome(self) -> CellOutcome {
+        CellOutcome {
+            constructions: self.constructions,
+            measurements: self.measurements,
+            status: Status::Ok,
         }
     }
 }
 
-/// A function to measure the runtimes of multiple executions of an algorithm.
+/// A function to measure all executions of a cell.
 ///
-/// It takes a `pattern` and a `text` and executes a function `f` using
-/// the standard signature of the pattern matching algorithms
-/// `(&[u8], &[u8]) -> Vec<usize>`.
+/// It takes the index of the pattern the algorithm is measured with, which
+/// is `None` for multiple pattern algorithms as they are measured with all
+/// patterns at once. Indices required by the algorithm are taken from the
+/// given `index_cache`. Every event while measuring the cell is passed to
+/// `on_event`.
 ///
-/// It returns a `(Vec<Duration>, usize)`, the runtimes of the exeuctions
-/// of the given functions and the number of matches.
-fn measure_exeuctions(
-    pattern: &[u8],
+/// No further executions are started once the `deadline` has passed, the
+/// measurements are discarded then and the cell is skipped.
+#[allow(clippy::too_many_arguments)]
+pub fn measure_cell(
+    algorithm_fn: &TypedAlgorithm,
     text: &[u8],
-    f: &TypedAlgorithm,
+    patterns: &[Vec<u8>],
+    pattern_index: Option<usize>,
+    index_cache: &mut IndexCache,
     cli_params: &CLIParams,
-) -> Vec<SingleMeasurement> {
-    let mut single_measurements: Vec<SingleMeasurement> = Vec::new();
-
-    for _ in 0..cli_params.executions {
-        single_measurements.push(match f {
-            TypedAlgorithm::SinglePatternAlgorithm(f) => f.measure(pattern, text, cli_params),
-            TypedAlgorithm::MultiplePatternAlgorithm(_) => {
-                // This case should not occur here because of the matching in
-                // run_measurement()
-                unimplemented!();
-            }
-            TypedAlgorithm::SuffixArrayAlgorithm(f) => f.measure(pattern, text, cli_params),
-            TypedAlgorithm::BWTAlgorithm(f) => f.measure(pattern, text, cli_params),
-            TypedAlgorithm::ApproximativeAlgorithm(f) => f.measure(pattern, text, cli_params),
+    deadline: Option<Instant>,
+    on_event: &mut dyn FnMut(CellEvent),
+) -> CellOutcome {
+    let mut progress = CellProgress::default();
+
+    while !progress.finished(cli_params) {
+        if deadline.is_some_and(|x| Instant::now() >= x) {
+            let mut outcome = progress.into_outcome();
+
+            outcome.measurements.clear();
+            outcome.status = Status::Skipped;
+
+            return outcome;
+        }
+
+        progress.measure_execution(
+            algorithm_fn,
+            text,
+            patterns,
+            pattern_index,
+            index_cache,
+            cli_params,
+            on_event,
+        );
+    }
+
+    progress.into_outcome()
+}
+
+/// A function to measure a single execution of a cell.
+///
+/// Indices required by the algorithm are taken from `index_cache`, where
+/// they have to be built already using `CellProgress::build_index()`.
+fn measure_execution(
+    algorithm_fn: &TypedAlgorithm,
+    text: &[u8],
+    patterns: &[Vec<u8>],
+    pattern_index: Option<usize>,
+    index_cache: &mut IndexCache,
+    cli_params: &CLIParams,
+) -> SingleMeasurement {
+    // Unwrapping the pattern index is safe here as only multiple pattern
+    // algorithms are measured without one
+    match algorithm_fn {
+        TypedAlgorithm::MultiplePatternAlgorithm(f) => f.measure(patterns, text, cli_params),
+        TypedAlgorithm::SinglePatternAlgorithm(f) => {
+            f.measure(&patterns[pattern_index.unwrap()], text, cli_params)
+        }
+        TypedAlgorithm::ApproximativeAlgorithm(f) => {
+            f.measure(&patterns[pattern_index.unwrap()], text, cli_params)
+        }
+        TypedAlgorithm::SuffixArrayAlgorithm(f) => {
+            let index = index_cache.get(text, cli_params, false);
+
+            f.measure(&patterns[pattern_index.unwrap()], index, cli_params)
+        }
+        TypedAlgorithm::BWTAlgorithm(f) => {
+            let index = index_cache.get(text, cli_params, true);
+
+            f.measure(&patterns[pattern_index.unwrap()], index, cli_params)
+        }
+    }
+}
+
+/// Creates a `MeasurementResult` from the measurements of all executions of
+/// an algorithm.
+fn measurement_result(
+    algorithm: &str,
+    text_length: usize,
+    pattern_length: usize,
+    measurements: Vec<SingleMeasurement>,
+    index_query: bool,
+    cli_params: &CLIParams,
+) -> MeasurementResult {
+    MeasurementResult::new(
+        algorithm,
+        text_length,
+        pattern_length,
+        index_query,
+        measurements,
+        // Unwrapping is safe here because of the checks done in cli_params.valid()
+        cli_params.outlier_detection.unwrap(),
+        cli_params.exclude_outliers,
+        cli_params.pinned_cpu,
+        cli_params.threads,
+        cli_params.query_mode.unwrap(),
+        cli_params.cache_mode.unwrap(),
+    )
+}
+
+/// A function to measure a single phase of an algorithm.
+///
+/// It measures the runtime of `f` and, if memory tracking is enabled, the
+/// heap memory used by `f` in a separate execution after the timed one, so
+/// that tracking the allocations doesn't distort the runtime and the timed
+/// execution isn't run on caches warmed by the untracked execution.
+///
+/// Depending on the cache mode, the caches are evicted before `f` is timed,
+/// so that `f` is executed only once instead of in a batch, or `f` is
+/// executed once more without being timed to warm up the caches.
+///
+/// It returns the result of the timed execution of `f` and the measurement.
+pub fn measure_phase<R, F: FnMut() -> R>(
+    cli_params: &CLIParams,
+    mut f: F,
+) -> (R, PhaseMeasurement) {
+    // Unwrapping is safe here because of the checks done in cli_params.valid()
+    let (result, duration) = match cli_params.cache_mode.unwrap() {
+        CacheMode::Cold => {
+            evict_caches();
+
+            timer::measure_once(&mut f)
+        }
+        CacheMode::Warm => {
+            black_box(f());
+
+            timer::measure(&mut f)
+        }
+        CacheMode::None => timer::measure(&mut f),
+    };
+
+    let memory = if cli_params.track_memory {
+        Some(measure_memory(f).1)
+    } else {
+        None
+    };
+
+    (result, PhaseMeasurement { duration, memory })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::fs;
+
+    use super::*;
+    use crate::match_algorithm::match_algorithms;
+
+    const PATTERNS: [&[u8]; 4] = [b"abra", b"cad", b"dabr", b"ra"];
+
+    fn measurement(args: &[&str]) -> Measurement {
+        let sources = ["--tr", "220", "--pa", "abra", "cad", "dabr", "ra"];
+        let args: Vec<&str> = ["aas-benchmark"]
+            .iter()
+            .chain(sources.iter())
+            .chain(args)
+            .copied()
+            .collect();
+        let cli_params = CLIParams::from_args(&args);
+
+        assert!(cli_params.valid());
+
+        Measurement::new(
+            match_algorithms(&cli_params.algorithms),
+            b"abracadabra".repeat(20),
+            PATTERNS.iter().map(|x| x.to_vec()).collect(),
+            cli_params,
+        )
+    }
+
+    /// Measures all cells interleaved and returns the cell indices and
+    /// outcomes in the order they have been reported.
+    fn interleaved_outcomes(measurement: &Measurement) -> Vec<(usize, CellOutcome)> {
+        let cells = measurement.cells();
+        let outcomes = RefCell::new(Vec::new());
+
+        measurement.measure_interleaved_cells(
+            &cells,
+            &vec![None; cells.len()],
+            &Progress::new(cells.len(), false),
+            &Budget::new(&measurement.cli_params),
+            &|cell_index, outcome| outcomes.borrow_mut().push((cell_index, outcome)),
+        );
+
+        outcomes.into_inner()
+    }
+
+    /// Runs a whole measurement writing the raw results to a CSV file and
+    /// returns its rows.
+    fn csv_rows(args: &[&str]) -> Vec<HashMap<String, String>> {
+        let directory = tempfile::tempdir().unwrap();
+        let file_name = directory.path().join("results.csv");
+        let file_name = file_name.to_str().unwrap();
+
+        let args: Vec<&str> = args.iter().chain(&["-o", file_name]).copied().collect();
+        measurement(&args).run_measurement().unwrap();
+
+        csv::Reader::from_path(file_name)
+            .unwrap()
+            .deserialize()
+            .map(|x| x.unwrap())
+            .collect()
+    }
+
+    /// Returns the columns of the given rows identifying the cell and the
+    /// execution together with the results that don't vary between runs.
+    fn cell_columns(rows: &[HashMap<String, String>]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| {
+                [
+                    "algorithm",
+                    "pattern_length",
+                    "status",
+                    "execution",
+                    "matches",
+                ]
+                .iter()
+                .map(|column| row[*column].clone())
+                .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the number of occurrences of `pattern` in `text`.
+    fn occurrences(pattern: &[u8], text: &[u8]) -> usize {
+        text.windows(pattern.len())
+            .filter(|x| x == &pattern)
+            .count()
+    }
+
+    #[test]
+    fn test_interleaved_order() {
+        // With a single execution per cell, all cells are reported right
+        // after the first round, in the order they have been executed in
+        let order = |seed: &str| -> Vec<usize> {
+            let args = [
+                "-a",
+                "naive",
+                "horspool",
+                "kmp",
+                "--shuffle",
+                "-n",
+                "1",
+                "-s",
+                seed,
+            ];
+
+            interleaved_outcomes(&measurement(&args))
+                .iter()
+                .map(|x| x.0)
+                .collect()
+        };
+
+        let order_42 = order("42");
+        let mut sorted = order_42.clone();
+        sorted.sort_unstable();
+
+        assert_eq!(order_42, order("42"));
+        assert_ne!(order_42, order("43"));
+        assert_ne!(order_42, sorted);
+        assert_eq!(sorted, (0..12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_interleaved_executions() {
+        let args = [
+            "-a",
+            "naive",
+            "horspool",
+            "sa-match",
+            "--shuffle",
+            "-n",
+            "3",
+            "-s",
+            "7",
+        ];
+        let measurement = measurement(&args);
+        let cells = measurement.cells();
+
+        let mut outcomes = interleaved_outcomes(&measurement);
+        outcomes.sort_by_key(|x| x.0);
+
+        // Every cell is reported once with all of its executions
+        assert_eq!(
+            outcomes.iter().map(|x| x.0).collect::<Vec<_>>(),
+            (0..cells.len()).collect::<Vec<_>>()
+        );
+
+        for (cell_index, outcome) in outcomes {
+            // Unwrapping is safe here as only single pattern algorithms are
+            // measured
+            let pattern = PATTERNS[cells[cell_index].pattern_index.unwrap()];
+            let expected = occurrences(pattern, &measurement.text);
+
+            assert_eq!(outcome.status, Status::Ok);
+            assert_eq!(outcome.measurements.len(), 3);
+            assert!(outcome.measurements.iter().all(|x| x.matches == expected));
+        }
+    }
+
+    #[test]
+    fn test_measure_phase_memory_after_timing() {
+        let cli_params = CLIParams::from_args(&[
+            "aas-benchmark",
+            "-a",
+            "naive",
+            "--tr",
+            "220",
+            "--pa",
+            "abra",
+            "--cache",
+            "cold",
+            "--memory",
+        ]);
+        let mut executions = 0;
+
+        let (result, phase) = measure_phase(&cli_params, || {
+            executions += 1;
+            executions
         });
+
+        // The timed execution comes first, so that it runs on evicted caches
+        assert_eq!(result, 1);
+        assert_eq!(executions, 2);
+        assert!(phase.memory.is_some());
     }
 
-    single_measurements
+    #[test]
+    fn test_cell_deadline() {
+        let measurement = measurement(&["-a", "naive", "-n", "3"]);
+        let measure = |deadline: Instant| {
+            measure_cell(
+                &measurement.algorithms[0].1,
+                &measurement.text,
+                &measurement.patterns,
+                Some(0),
+                &mut IndexCache::default(),
+                &measurement.cli_params,
+                Some(deadline),
+                &mut |_| (),
+            )
+        };
+
+        // A cell still running once the total budget is used up is skipped
+        let outcome = measure(Instant::now());
+
+        assert_eq!(outcome.status, Status::Skipped);
+        assert!(outcome.measurements.is_empty());
+
+        let outcome = measure(Instant::now() + Duration::from_secs(3600));
+
+        assert_eq!(outcome.status, Status::Ok);
+        assert_eq!(outcome.measurements.len(), 3);
+    }
+
+    #[test]
+    fn test_parallel_cells() {
+        let args = ["-a", "naive", "kmp", "sa-match", "aho-corasick", "-n", "2"];
+        let serial = csv_rows(&args);
+        let parallel = csv_rows(&[&args[..], &["--threads", "4"]].concat());
+
+        // The same cells are measured with the same results and printed in
+        // the same order, only the number of threads differs
+        assert_eq!(cell_columns(&serial), cell_columns(&parallel));
+        assert!(serial.len() > 10);
+        assert!(serial.iter().all(|row| row["threads"] == "1"));
+        assert!(parallel.iter().all(|row| row["threads"] == "4"));
+    }
+
+    #[test]
+    fn test_aggregate_results() {
+        let args = [
+            "-a",
+            "naive",
+            "kmp",
+            "aho-corasick",
+            "-n",
+            "3",
+            "--aggregate",
+        ];
+        let rows = csv_rows(&args);
+
+        let mut cells: Vec<(&str, &str)> = rows
+            .iter()
+            .map(|row| (row["algorithm"].as_str(), row["pattern_length"].as_str()))
+            .collect();
+        cells.dedup();
+
+        // A single row per algorithm and pattern, the multiple pattern
+        // algorithm being measured with all patterns at once
+        assert_eq!(rows.len(), 2 * PATTERNS.len() + 1);
+        assert_eq!(cells.len(), rows.len());
+
+        let text = b"abracadabra".repeat(20);
+
+        for (row, pattern) in rows.iter().zip(PATTERNS.iter().cycle()) {
+            assert_eq!(row["text_length"], "220");
+            assert_eq!(row["status"], "ok");
+            assert_eq!(row["executions"], "3");
+            assert!(!row.contains_key("execution"));
+
+            for column in [
+                "time_mean_ns",
+                "time_min_ns",
+                "time_max_ns",
+                "time_stddev_ns",
+            ] {
+                assert!(row[column].parse::<f64>().is_ok());
+            }
+
+            // Multiple pattern algorithms don't have a preparation phase
+            if row["algorithm"] == "Aho-Corasick" {
+                assert_eq!(row["prep_mean_ns"], "");
+            } else {
+                assert!(row["prep_mean_ns"].parse::<f64>().is_ok());
+                assert!(row["prep_ci95_high_ns"].parse::<f64>().is_ok());
+                assert_eq!(row["matches"], occurrences(pattern, &text).to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn test_raw_results() {
+        let rows = csv_rows(&["-a", "naive", "kmp", "aho-corasick", "-n", "3"]);
+
+        let header = "algorithm,text_length,pattern_length,status,cpu,threads,query,cache,\
+            execution,executions,matches,prep_time_ns,time_ns,prep_peak_bytes,\
+            prep_allocations,peak_bytes,allocations,comparisons,inspected_chars,shifts,\
+            shift_distance,mean_shift,mb_per_s,ns_per_byte,ns_per_pattern_char,\
+            ns_per_occurrence,outlier";
+        let mut columns: Vec<&str> = header.split(',').collect();
+        columns.sort_unstable();
+
+        // One row per execution with the columns of the raw records
+        assert_eq!(rows.len(), 3 * (2 * PATTERNS.len() + 1));
+
+        for (i, row) in rows.iter().enumerate() {
+            let mut row_columns: Vec<&str> = row.keys().map(|x| x.as_str()).collect();
+            row_columns.sort_unstable();
+
+            assert_eq!(row_columns, columns);
+            assert_eq!(row["execution"], (i % 3).to_string());
+            assert_eq!(row["executions"], "3");
+            assert!(row["prep_time_ns"].parse::<u128>().is_ok());
+            assert!(row["time_ns"].parse::<u128>().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_resume_results() {
+        let directory = tempfile::tempdir().unwrap();
+        let results_file = directory.path().join("results.csv");
+        let results_file = results_file.to_str().unwrap();
+        let checkpoint_file = directory.path().join("checkpoint.json");
+        let checkpoint_file = checkpoint_file.to_str().unwrap();
+
+        let args = [
+            "-a",
+            "naive",
+            "sa-match",
+            "aho-corasick",
+            "-n",
+            "2",
+            "-o",
+            results_file,
+            "--checkpoint",
+            checkpoint_file,
+        ];
+        let resume_args = [&args[..], &["--resume"]].concat();
+        let rows = || -> Vec<HashMap<String, String>> {
+            csv::Reader::from_path(results_file)
+                .unwrap()
+                .deserialize()
+                .map(|x| x.unwrap())
+                .collect()
+        };
+
+        measurement(&args).run_measurement().unwrap();
+
+        let results = fs::read_to_string(results_file).unwrap();
+        let checkpoint = fs::read_to_string(checkpoint_file).unwrap();
+        let complete_rows = rows();
+
+        // An existing checkpoint is neither resumed nor overwritten by
+        // accident, and neither are the results
+        assert!(measurement(&args).run_measurement().is_err());
+        assert_eq!(fs::read_to_string(results_file).unwrap(), results);
+
+        // Resuming a complete measurement doesn't measure anything again
+        measurement(&resume_args).run_measurement().unwrap();
+        assert_eq!(fs::read_to_string(results_file).unwrap(), results);
+
+        // Simulate an abort in the middle of a record
+        fs::write(results_file, &results[..results.len() / 2]).unwrap();
+
+        measurement(&resume_args).run_measurement().unwrap();
+
+        // Every cell is written once, including the index construction, and
+        // the checkpoint is left as it is
+        let resumed_rows = rows();
+
+        assert_eq!(cell_columns(&resumed_rows), cell_columns(&complete_rows));
+        assert_eq!(resumed_rows[0], complete_rows[0]);
+        assert_eq!(fs::read_to_string(checkpoint_file).unwrap(), checkpoint);
+
+        // The checkpoint only matches the same configuration
+        let args = [&resume_args[..], &["--query", "count"]].concat();
+        assert!(measurement(&args).run_measurement().is_err());
+    }
+
+    #[test]
+    fn test_resume_all_algorithms() {
+        let directory = tempfile::tempdir().unwrap();
+        let results_file = directory.path().join("results.jsonl");
+        let results_file = results_file.to_str().unwrap();
+        let checkpoint_file = directory.path().join("checkpoint.json");
+        let checkpoint_file = checkpoint_file.to_str().unwrap();
+
+        let args = [
+            "-a",
+            "all",
+            "-n",
+            "1",
+            "-o",
+            results_file,
+            "--checkpoint",
+            checkpoint_file,
+        ];
+        let mut first_run = measurement(&args);
+
+        // The order of all algorithms is the same in every process
+        let names: Vec<&str> = first_run.algorithms.iter().map(|x| x.0.as_str()).collect();
+        let mut sorted_names = names.clone();
+        sorted_names.sort_unstable();
+
+        assert_eq!(names, sorted_names);
+
+        first_run.run_measurement().unwrap();
+
+        let results = fs::read_to_string(results_file).unwrap();
+        fs::write(results_file, &results[..results.len() / 2]).unwrap();
+
+        let resume_args = [&args[..], &["--resume"]].concat();
+        measurement(&resume_args).run_measurement().unwrap();
+
+        let lines = |contents: &str| -> Vec<(String, u64)> {
+            contents
+                .lines()
+                .map(|line| {
+                    let record: serde_json::Value = serde_json::from_str(line).unwrap();
+
+                    (
+                        record["algorithm"]["argument"]
+                            .as_str()
+                            .unwrap()
+                            .to_string(),
+                        record["pattern"]["length"].as_u64().unwrap(),
+                    )
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            lines(&fs::read_to_string(results_file).unwrap()),
+            lines(&results)
+        );
+    }
 }
@@ -1,94 +1,471 @@
-use std::error::Error;
-use std::io;
+use std::io::Write;
 use std::time::Duration;
 
-use csv::WriterBuilder;
+use serde::{Deserialize, Serialize};
 
-use crate::match_algorithm::algorithm_name;
-use crate::measure::calculate_avg_duration;
-use crate::measure::csv_record::CSVRecord;
+use crate::algorithms::occurrences::QueryMode;
+use crate::cli::CLIParams;
+use crate::match_algorithm::{algorithm_name, match_algorithm};
+use crate::measure::cache::CacheMode;
+use crate::measure::chart::{charted, ChartSample};
+use crate::measure::counters::OperationCounts;
+use crate::measure::csv_record::{CSVRecord, SummaryCSVRecord};
+use crate::measure::json_record::{
+    JSONContext, JSONRecord, PhaseRecord, PhaseSummaryRecord, SearchRecord, SearchSummaryRecord,
+    SummaryJSONRecord,
+};
+use crate::measure::measurement::SingleMeasurement;
+use crate::measure::memory::MemoryUsage;
+use crate::measure::output::{OutputFormat, RecordWriter};
+use crate::measure::report::ReportEntry;
+use crate::measure::statistics::{durations_as_nanos, OutlierDetection, Summary};
+use crate::measure::throughput::Throughput;
+
+/// The status of the measurement of an algorithm.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// All executions have been measured successfully.
+    Ok,
+    /// The algorithm has panicked or aborted, e. g. because an allocation
+    /// failed.
+    Panic,
+    /// The process measuring the algorithm has been killed, e. g. by the
+    /// kernel running out of memory.
+    Killed,
+    /// The process measuring the algorithm has crashed otherwise.
+    Crash,
+    /// The measurement has been aborted because it took too long.
+    Timeout,
+    /// The algorithm hasn't been measured, e. g. because it has already
+    /// timed out with a shorter pattern.
+    Skipped,
+}
 
 /// A struct containg the measurement results of one or multiple
 /// algorithm executions of the same algorithm.
 pub struct MeasurementResult {
+    algorithm: String,
     algorithm_name: String,
 
     text_length: usize,
     pattern_length: usize,
 
-    matches: usize,
+    status: Status,
+    cpu: Option<usize>,
+    threads: usize,
+    query_mode: QueryMode,
+    cache_mode: CacheMode,
+
+    matches: Option<usize>,
+    index_query: bool,
 
     preparation_durations: Vec<Option<Duration>>,
     algorithm_durations: Vec<Duration>,
-    //avg_preparation_duration: f64,
-    avg_algorithm_duration: f64,
+    preparation_memory: Vec<Option<MemoryUsage>>,
+    algorithm_memory: Vec<Option<MemoryUsage>>,
+    algorithm_counts: Vec<Option<OperationCounts>>,
+    outliers: Vec<bool>,
+
+    preparation_summary: Option<Summary>,
+    algorithm_summary: Option<Summary>,
 }
 
 impl MeasurementResult {
-    /// Initalizes a new `MeasureResult` and calculates the average duration
-    /// of the given durations.
+    /// Initalizes a new `MeasureResult`, classifies the executions as outliers
+    /// or not and calculates the summary statistics of the given durations.
     ///
-    /// It takes the CLI paramter name of an algorithm and the durations of
-    /// the measured executions.
+    /// It takes the CLI paramter name of an algorithm and the measurements of
+    /// all executions. An execution is classified as an outlier if
+    /// either its preparation or its algorithm duration is an outlier. If
+    /// `exclude_outliers` is set, outliers are not taken into account for
+    /// the summary statistics. `index_query` tells whether the algorithm
+    /// queries a full-text index instead of scanning the text, `cpu` is
+    /// the CPU the measuring thread has been pinned to, if any, and `threads`
+    /// is the number of cells that have been measured in parallel. The query
+    /// and cache modes are recorded in every row.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         algorithm: &str,
         text_length: usize,
         pattern_length: usize,
-        matches: usize,
-        preparation_durations: Vec<Option<Duration>>,
-        algorithm_durations: Vec<Duration>,
+        index_query: bool,
+        measurements: Vec<SingleMeasurement>,
+        outlier_detection: OutlierDetection,
+        exclude_outliers: bool,
+        cpu: Option<usize>,
+        threads: usize,
+        query_mode: QueryMode,
+        cache_mode: CacheMode,
     ) -> Self {
-        let mut new = Self {
+        // All executions find the same matches
+        let matches = measurements.first().map(|x| x.matches);
+
+        let preparation_durations: Vec<Option<Duration>> = measurements
+            .iter()
+            .map(|x| x.preparation.map(|x| x.duration))
+            .collect();
+        let algorithm_durations: Vec<Duration> =
+            measurements.iter().map(|x| x.algorithm.duration).collect();
+        let preparation_memory = measurements
+            .iter()
+            .map(|x| x.preparation.and_then(|x| x.memory))
+            .collect();
+        let algorithm_memory = measurements.iter().map(|x| x.algorithm.memory).collect();
+        let algorithm_counts = measurements.iter().map(|x| x.counts).collect();
+
+        let outliers = classify_outliers(
+            &preparation_durations,
+            &algorithm_durations,
+            outlier_detection,
+        );
+
+        // Algorithms without a preparation phase don't have any preparation
+        // durations, so there won't be a summary for them
+        let included_preparation_durations: Vec<Duration> = preparation_durations
+            .iter()
+            .zip(outliers.iter())
+            .filter(|(_, outlier)| !exclude_outliers || !**outlier)
+            .filter_map(|(duration, _)| *duration)
+            .collect();
+        let included_algorithm_durations: Vec<Duration> = algorithm_durations
+            .iter()
+            .zip(outliers.iter())
+            .filter(|(_, outlier)| !exclude_outliers || !**outlier)
+            .map(|(duration, _)| *duration)
+            .collect();
+
+        let preparation_summary =
+            Summary::new(&durations_as_nanos(&included_preparation_durations));
+        let algorithm_summary = Summary::new(&durations_as_nanos(&included_algorithm_durations));
+
+        Self {
+            algorithm: String::from(algorithm),
             algorithm_name: String::from(algorithm_name(algorithm)),
 
             text_length,
             pattern_length,
 
+            status: Status::Ok,
+            cpu,
+            threads,
+            query_mode,
+            cache_mode,
+
             matches,
+            index_query,
 
             preparation_durations,
             algorithm_durations,
-            //avg_preparation_duration: 0f64,
-            avg_algorithm_duration: 0f64,
-        };
+            preparation_memory,
+            algorithm_memory,
+            algorithm_counts,
+            outliers,
+
+            preparation_summary,
+            algorithm_summary,
+        }
+    }
+
+    /// Initializes a new `MeasurementResult` of an algorithm that couldn't be
+    /// measured because of the given failure `status`.
+    ///
+    /// It doesn't contain any executions.
+    #[allow(clippy::too_many_arguments)]
+    pub fn failed(
+        algorithm: &str,
+        text_length: usize,
+        pattern_length: usize,
+        status: Status,
+        cpu: Option<usize>,
+        threads: usize,
+        query_mode: QueryMode,
+        cache_mode: CacheMode,
+    ) -> Self {
+        Self {
+            algorithm: String::from(algorithm),
+            algorithm_name: String::from(algorithm_name(algorithm)),
+
+            text_length,
+            pattern_length,
+
+            status,
+            cpu,
+            threads,
+            query_mode,
+            cache_mode,
+
+            matches: None,
+            index_query: false,
+
+            preparation_durations: Vec::new(),
+            algorithm_durations: Vec::new(),
+            preparation_memory: Vec::new(),
+            algorithm_memory: Vec::new(),
+            algorithm_counts: Vec::new(),
+            outliers: Vec::new(),
+
+            preparation_summary: None,
+            algorithm_summary: None,
+        }
+    }
+
+    /// Returns a record containing the summary statistics of all executions.
+    fn summary_csv_record(&self) -> SummaryCSVRecord<'_> {
+        SummaryCSVRecord::new(
+            &self.algorithm_name,
+            self.text_length,
+            self.pattern_length,
+            self.algorithm_durations.len(),
+            self.outliers.iter().filter(|x| **x).count(),
+            self.matches,
+            self.status,
+            self.cpu,
+            self.threads,
+            self.query_mode,
+            self.cache_mode,
+            self.preparation_summary.as_ref(),
+            self.algorithm_summary.as_ref(),
+            max_memory_usage(&self.preparation_memory),
+            max_memory_usage(&self.algorithm_memory),
+            // All executions perform the same operations
+            self.algorithm_counts.first().copied().flatten(),
+            self.throughput(self.algorithm_summary.as_ref().map_or(0f64, |x| x.mean)),
+        )
+    }
+
+    /// Returns the entry of this result in the report, `None` for index
+    /// constructions, which don't have a search phase to compare.
+    pub fn report_entry(&self) -> Option<ReportEntry<'_>> {
+        match_algorithm(&self.algorithm)?;
+
+        let median = self.algorithm_summary.as_ref().map(|x| x.median);
+
+        Some(ReportEntry {
+            algorithm: &self.algorithm,
+            algorithm_name: &self.algorithm_name,
+            text_length: self.text_length,
+            pattern_length: self.pattern_length,
+            status: self.status,
+            preparation_median: self.preparation_summary.as_ref().map(|x| x.median),
+            median,
+            mb_per_s: median.and_then(|x| self.throughput(x).mb_per_s),
+        })
+    }
+
+    /// Returns a chart sample for each execution of this result that is
+    /// `charted`, e. g. leaving out the outliers if `exclude_outliers` is set.
+    pub fn chart_samples(&self, exclude_outliers: bool) -> Vec<ChartSample> {
+        self.preparation_durations
+            .iter()
+            .zip(self.algorithm_durations.iter())
+            .zip(self.outliers.iter())
+            .filter(|(_, outlier)| {
+                charted(&self.algorithm, self.status, **outlier, exclude_outliers)
+            })
+            .map(
+                |((preparation_duration, algorithm_duration), _)| ChartSample {
+                    algorithm: self.algorithm_name.clone(),
+                    text_length: self.text_length,
+                    pattern_length: self.pattern_length,
+                    preparation_time_ns: preparation_duration.map_or(0f64, |x| x.as_nanos() as f64),
+                    time_ns: algorithm_duration.as_nanos() as f64,
+                },
+            )
+            .collect()
+    }
 
-        //new.avg_preparation_duration = calculate_avg_duration(&new.preparation_durations);
-        new.avg_algorithm_duration = calculate_avg_duration(&new.algorithm_durations);
+    /// Returns the normalized metrics of an algorithm runtime of `time_ns`
+    /// nanoseconds.
+    fn throughput(&self, time_ns: f64) -> Throughput {
+        Throughput::new(
+            time_ns,
+            self.text_length,
+            self.pattern_length,
+            self.matches.unwrap_or(0),
+            self.index_query,
+        )
+    }
 
-        new
+    /// Returns the context of the JSON records of this result.
+    fn json_context<'a>(&'a self, cli_params: &'a CLIParams) -> JSONContext<'a> {
+        JSONContext::new(
+            &self.algorithm_name,
+            &self.algorithm,
+            self.text_length,
+            self.pattern_length,
+            self.status,
+            self.cpu,
+            self.threads,
+            self.query_mode,
+            self.cache_mode,
+            cli_params,
+        )
     }
 
-    pub fn print_csv(&self, print_header: bool) -> Result<(), Box<dyn Error>> {
-        let mut wtr = WriterBuilder::new()
-            .has_headers(print_header)
-            .from_writer(io::stdout());
+    /// Writes a record of each execution in the format of the given
+    /// `writer`, the JSON records including the parameters and input
+    /// sources given by `cli_params`.
+    pub fn write_records<W: Write>(
+        &self,
+        writer: &mut RecordWriter<W>,
+        cli_params: &CLIParams,
+    ) -> Result<(), String> {
+        let json = writer.format() != OutputFormat::Csv;
+
+        // Failed measurements are written as a single record without any
+        // durations
+        if self.status != Status::Ok {
+            if json {
+                writer.write(&JSONRecord::failed(self.json_context(cli_params)))?;
+            } else {
+                writer.write(&CSVRecord::failed(
+                    &self.algorithm_name,
+                    self.text_length,
+                    self.pattern_length,
+                    self.status,
+                    self.cpu,
+                    self.threads,
+                    self.query_mode,
+                    self.cache_mode,
+                ))?;
+            }
+        }
 
-        // Zip preparation durations and algorithm duration together
-        // to iterate over both vectors at the same time
+        // Zip preparation durations, algorithm durations and outlier flags
+        // together to iterate over all vectors at the same time
         let zipped = self
             .preparation_durations
             .iter()
-            .zip(self.algorithm_durations.iter());
-
-        for (execution, (preparation_duration, algorithm_duration)) in zipped.enumerate() {
-            let preparation_time_ms = preparation_duration
-                .unwrap_or(Duration::new(0, 0))
-                .as_millis();
-            let algorithm_time_ms = algorithm_duration.as_millis();
-
-            wtr.serialize(CSVRecord::new(
-                &self.algorithm_name,
-                self.text_length,
-                self.pattern_length,
-                execution,
-                self.matches,
-                preparation_time_ms,
-                algorithm_time_ms,
-            ))?;
-        }
+            .zip(self.algorithm_durations.iter())
+            .zip(self.outliers.iter());
+
+        for (execution, ((preparation_duration, algorithm_duration), outlier)) in zipped.enumerate()
+        {
+            let preparation_memory = self.preparation_memory[execution];
+            let algorithm_memory = self.algorithm_memory[execution];
+            let algorithm_counts = self.algorithm_counts[execution];
+            let algorithm_time_ns = algorithm_duration.as_nanos();
+            let throughput = self.throughput(algorithm_time_ns as f64);
+
+            if json {
+                writer.write(&JSONRecord::new(
+                    self.json_context(cli_params),
+                    execution,
+                    self.algorithm_durations.len(),
+                    self.matches.unwrap_or(0),
+                    *outlier,
+                    preparation_duration.map(|x| PhaseRecord {
+                        time_ns: x.as_nanos(),
+                        memory: preparation_memory,
+                    }),
+                    SearchRecord {
+                        time_ns: algorithm_time_ns,
+                        memory: algorithm_memory,
+                        counts: algorithm_counts,
+                        mean_shift: algorithm_counts.and_then(|x| x.mean_shift()),
+                        throughput,
+                    },
+                ))?;
+            } else {
+                let preparation_time_ns = preparation_duration
+                    .unwrap_or(Duration::new(0, 0))
+                    .as_nanos();
 
-        wtr.flush()?;
+                writer.write(&CSVRecord::new(
+                    &self.algorithm_name,
+                    self.text_length,
+                    self.pattern_length,
+                    execution,
+                    self.algorithm_durations.len(),
+                    self.matches.unwrap_or(0),
+                    preparation_time_ns,
+                    algorithm_time_ns,
+                    preparation_memory,
+                    algorithm_memory,
+                    algorithm_counts,
+                    throughput,
+                    *outlier,
+                    self.cpu,
+                    self.threads,
+                    self.query_mode,
+                    self.cache_mode,
+                ))?;
+            }
+        }
 
         Ok(())
     }
+
+    /// Writes a record containing the summary statistics of all executions
+    /// in the format of the given `writer`.
+    pub fn write_summary_record<W: Write>(
+        &self,
+        writer: &mut RecordWriter<W>,
+        cli_params: &CLIParams,
+    ) -> Result<(), String> {
+        if writer.format() == OutputFormat::Csv {
+            return writer.write(&self.summary_csv_record());
+        }
+
+        // All executions perform the same operations
+        let counts = self.algorithm_counts.first().copied().flatten();
+
+        writer.write(&SummaryJSONRecord::new(
+            self.json_context(cli_params),
+            self.algorithm_durations.len(),
+            self.outliers.iter().filter(|x| **x).count(),
+            self.matches,
+            self.preparation_summary
+                .as_ref()
+                .map(|summary| PhaseSummaryRecord {
+                    time_ns: summary,
+                    memory: max_memory_usage(&self.preparation_memory),
+                }),
+            self.algorithm_summary
+                .as_ref()
+                .map(|summary| SearchSummaryRecord {
+                    time_ns: summary,
+                    memory: max_memory_usage(&self.algorithm_memory),
+                    counts,
+                    mean_shift: counts.and_then(|x| x.mean_shift()),
+                    throughput: self.throughput(summary.mean),
+                }),
+        ))
+    }
+}
+
+/// Classifies executions as outliers if either their preparation or their
+/// algorithm duration is an outlier according to the given method.
+fn classify_outliers(
+    preparation_durations: &[Option<Duration>],
+    algorithm_durations: &[Duration],
+    outlier_detection: OutlierDetection,
+) -> Vec<bool> {
+    let mut outliers = outlier_detection.classify(&durations_as_nanos(algorithm_durations));
+
+    // Only classify preparation durations if all executions have been
+    // measured with a preparation phase
+    let measured_preparation_durations: Option<Vec<Duration>> =
+        preparation_durations.iter().copied().collect();
+
+    if let Some(measured_preparation_durations) = measured_preparation_durations {
+        let preparation_outliers =
+            outlier_detection.classify(&durations_as_nanos(&measured_preparation_durations));
+
+        for (outlier, preparation_outlier) in outliers.iter_mut().zip(preparation_outliers) {
+            *outlier |= preparation_outlier;
+        }
+    }
+
+    outliers
+}
+
+/// Returns the maximum peak memory and number of allocations of all
+/// executions, `None` if memory hasn't been tracked.
+fn max_memory_usage(memory: &[Option<MemoryUsage>]) -> Option<MemoryUsage> {
+    memory.iter().flatten().copied().reduce(|a, b| MemoryUsage {
+        peak_bytes: a.peak_bytes.max(b.peak_bytes),
+        allocations: a.allocations.max(b.allocations),
+    })
 }
@@ -0,0 +1,188 @@
+#[cfg(feature = "memory")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::hint::black_box;
+
+use serde::{Deserialize, Serialize};
+
+/// The heap memory used by a single phase of an algorithm.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct MemoryUsage {
+    /// The maximum number of bytes allocated at the same time during the
+    /// phase, not counting memory allocated before the phase.
+    pub peak_bytes: usize,
+    /// The number of allocations and reallocations during the phase.
+    pub allocations: usize,
+}
+
+/// The allocation statistics of a thread while tracking is enabled.
+///
+/// Without the `memory` feature, nothing is ever recorded, so only the
+/// fields returned by `measure_memory()` are read.
+#[derive(Clone, Copy)]
+#[cfg_attr(not(feature = "memory"), allow(dead_code))]
+struct TrackingState {
+    tracking: bool,
+    current_bytes: isize,
+    peak_bytes: isize,
+    allocations: usize,
+}
+
+impl TrackingState {
+    const fn new() -> Self {
+        Self {
+            tracking: false,
+            current_bytes: 0,
+            peak_bytes: 0,
+            allocations: 0,
+        }
+    }
+}
+
+thread_local! {
+    // Initialized as a constant so that accessing it never allocates, which
+    // would recurse into the allocator
+    static TRACKING_STATE: Cell<TrackingState> = const { Cell::new(TrackingState::new()) };
+}
+
+/// A global allocator forwarding all requests to the system allocator and
+/// counting the allocations of the current thread while tracking is enabled
+/// using `measure_memory()`.
+///
+/// Even while tracking is disabled, every allocation reads a thread-local
+/// flag, which slows down algorithms allocating in their timed phases. So
+/// the allocator is only installed if the program has been built with the
+/// `memory` feature.
+#[cfg(feature = "memory")]
+pub struct TrackingAllocator;
+
+#[cfg(feature = "memory")]
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+
+        record_deallocation(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+
+        new_ptr
+    }
+}
+
+/// Records an allocation of `allocated` bytes replacing `deallocated` bytes
+/// if tracking is enabled for the current thread.
+#[cfg(any(test, feature = "memory"))]
+fn record(allocated: usize, deallocated: usize) {
+    // The state can't be accessed anymore while the thread is being
+    // destroyed, allocations aren't tracked then anyway
+    let _ = TRACKING_STATE.try_with(|state| {
+        let mut current = state.get();
+
+        if current.tracking {
+            current.current_bytes += allocated as isize - deallocated as isize;
+            current.peak_bytes = current.peak_bytes.max(current.current_bytes);
+            current.allocations += 1;
+
+            state.set(current);
+        }
+    });
+}
+
+/// Records a deallocation of `deallocated` bytes if tracking is enabled for
+/// the current thread.
+#[cfg(any(test, feature = "memory"))]
+fn record_deallocation(deallocated: usize) {
+    let _ = TRACKING_STATE.try_with(|state| {
+        let mut current = state.get();
+
+        if current.tracking {
+            current.current_bytes -= deallocated as isize;
+
+            state.set(current);
+        }
+    });
+}
+
+/// Executes `f` while tracking the heap allocations of the current thread.
+///
+/// This requires `TrackingAllocator` to be the global allocator, i. e. the
+/// `memory` feature, otherwise no allocations are recorded.
+///
+/// It returns the result of `f` and the memory used during its execution.
+/// Memory allocated by `f` that is still in use by the result is counted.
+pub fn measure_memory<R, F: FnOnce() -> R>(f: F) -> (R, MemoryUsage) {
+    TRACKING_STATE.with(|state| {
+        state.set(TrackingState {
+            tracking: true,
+            ..TrackingState::new()
+        })
+    });
+
+    let result = black_box(f());
+
+    let state = TRACKING_STATE.with(|state| state.replace(TrackingState::new()));
+
+    (
+        result,
+        MemoryUsage {
+            peak_bytes: state.peak_bytes.max(0) as usize,
+            allocations: state.allocations,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        TRACKING_STATE.with(|state| {
+            state.set(TrackingState {
+                tracking: true,
+                ..TrackingState::new()
+            })
+        });
+
+        record(100, 0);
+        record(300, 100);
+        record_deallocation(300);
+        record(50, 0);
+
+        let state = TRACKING_STATE.with(|state| state.replace(TrackingState::new()));
+
+        assert_eq!(state.peak_bytes, 300);
+        assert_eq!(state.current_bytes, 50);
+        assert_eq!(state.allocations, 3);
+
+        // Nothing is recorded while tracking is disabled
+        record(100, 0);
+        assert_eq!(TRACKING_STATE.with(|state| state.get().allocations), 0);
+    }
+}
@@ -0,0 +1,180 @@
+use std::env;
+use std::fs::{self, File};
+use std::path::Path;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::cli::CLIParams;
+use crate::measure::cache::parse_cache_size;
+
+/// The context a measurement has been taken in, i. e. the build of this
+/// program, its parameters and the machine it ran on, so that the results
+/// can still be interpreted long after they have been archived.
+///
+/// Values that can't be read from the system, e. g. on operating systems
+/// other than Linux, are `None`.
+#[derive(Serialize)]
+pub struct Metadata<'a> {
+    pub version: &'static str,
+    /// `release` or `debug`, depending on whether the program has been built
+    /// with debug assertions.
+    pub profile: &'static str,
+    /// The enabled cargo features.
+    pub features: Vec<&'static str>,
+    pub command_line: Vec<String>,
+    /// The time the metadata has been collected at, i. e. right before the
+    /// measurement has started, in seconds since the Unix epoch.
+    pub timestamp: Option<u64>,
+    pub hostname: Option<String>,
+    pub kernel: Option<String>,
+    pub cpu: CpuInfo,
+    pub parameters: &'a CLIParams,
+}
+
+/// The CPU the measurement has been taken on.
+#[derive(Serialize)]
+pub struct CpuInfo {
+    pub model: Option<String>,
+    /// The number of CPUs available to this program.
+    pub cpus: Option<usize>,
+    pub caches: Vec<CacheInfo>,
+}
+
+/// A cache of the first CPU.
+#[derive(Serialize)]
+pub struct CacheInfo {
+    pub level: Option<u32>,
+    /// `Data`, `Instruction` or `Unified`.
+    #[serde(rename = "type")]
+    pub cache_type: Option<String>,
+    pub size_bytes: Option<usize>,
+}
+
+impl<'a> Metadata<'a> {
+    /// Collects the metadata of the current run with the given CLI
+    /// parameters.
+    pub fn collect(cli_params: &'a CLIParams) -> Self {
+        let mut features = Vec::new();
+
+        if cfg!(feature = "counters") {
+            features.push("counters");
+        }
+
+        if cfg!(feature = "memory") {
+            features.push("memory");
+        }
+
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            profile: if cfg!(debug_assertions) {
+                "debug"
+            } else {
+                "release"
+            },
+            features,
+            command_line: env::args().collect(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|x| x.as_secs()),
+            hostname: read_trimmed("/proc/sys/kernel/hostname"),
+            kernel: read_trimmed("/proc/sys/kernel/osrelease"),
+            cpu: CpuInfo {
+                model: fs::read_to_string("/proc/cpuinfo")
+                    .ok()
+                    .and_then(|cpuinfo| cpu_model(&cpuinfo)),
+                cpus: thread::available_parallelism().ok().map(|x| x.get()),
+                caches: caches(),
+            },
+            parameters: cli_params,
+        }
+    }
+
+    /// Writes the metadata as JSON to the file with the given name.
+    pub fn write(&self, file_name: &str) -> Result<(), String> {
+        let file = File::create(file_name).map_err(|error| error.to_string())?;
+
+        serde_json::to_writer_pretty(file, self).map_err(|error| error.to_string())
+    }
+}
+
+/// Reads a file containing a single value, returning `None` if it can't be
+/// read.
+fn read_trimmed<P: AsRef<Path>>(path: P) -> Option<String> {
+    fs::read_to_string(path).ok().map(|x| x.trim().to_string())
+}
+
+/// Returns the model name of the first CPU listed in the contents of
+/// `/proc/cpuinfo`.
+fn cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+
+        // x86 CPUs give a `model name`, some ARM CPUs only a `Model`
+        if ["model name", "Model"].contains(&key.trim()) {
+            Some(value.trim().to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the caches of the first CPU ordered by their level as reported
+/// by the kernel (Linux only).
+fn caches() -> Vec<CacheInfo> {
+    let mut directories: Vec<_> = fs::read_dir("/sys/devices/system/cpu/cpu0/cache")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("level").exists())
+        .collect();
+
+    // The directories are named `index0`, `index1` and so on
+    directories.sort();
+
+    directories
+        .iter()
+        .map(|directory| CacheInfo {
+            level: read_trimmed(directory.join("level")).and_then(|x| x.parse().ok()),
+            cache_type: read_trimmed(directory.join("type")),
+            size_bytes: read_trimmed(directory.join("size")).and_then(|x| parse_cache_size(&x)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel\t\t: 85\n\
+            model name\t: Intel(R) Xeon(R) Processor\n\nprocessor\t: 1\n\
+            model name\t: Intel(R) Xeon(R) Processor\n";
+
+        assert_eq!(
+            cpu_model(cpuinfo),
+            Some(String::from("Intel(R) Xeon(R) Processor"))
+        );
+        assert_eq!(cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn test_recorded_seed() {
+        let seed = |args: &[&str]| -> serde_json::Value {
+            let cli_params = CLIParams::from_args(args);
+            let metadata = serde_json::to_value(Metadata::collect(&cli_params)).unwrap();
+
+            metadata["parameters"]["seed"].clone()
+        };
+
+        assert_eq!(seed(&["aas-benchmark", "-a", "naive", "-s", "42"]), 42);
+
+        // A seed is drawn if none has been given, so that the run can be
+        // reproduced nevertheless
+        assert!(seed(&["aas-benchmark", "-a", "naive"]).is_u64());
+    }
+}
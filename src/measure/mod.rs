@@ -1,10 +1,30 @@
+pub mod budget;
+pub mod cache;
+pub mod chart;
+pub mod checkpoint;
+pub mod counters;
 pub mod csv_record;
+pub mod index_cache;
+pub mod isolation;
+pub mod json_record;
 pub mod measurement;
 pub mod measurement_result;
-
-use std::time::Duration;
+pub mod memory;
+pub mod metadata;
+pub mod output;
+pub mod progress;
+pub mod report;
+#[cfg(target_os = "linux")]
+pub mod scheduler;
+#[cfg(not(target_os = "linux"))]
+#[path = "scheduler_unsupported.rs"]
+pub mod scheduler;
+pub mod statistics;
+pub mod throughput;
+pub mod timer;
 
 use self::measurement::SingleMeasurement;
+use crate::algorithms::full_text_indices::full_text_index::FullTextIndex;
 use crate::cli::CLIParams;
 
 /// Trait for implementing a measurement.
@@ -31,19 +51,15 @@ pub trait MultiplePatternMeasure {
     ) -> SingleMeasurement;
 }
 
-/// A function to calculate the average duration of a `Vec<Duration>`
-/// in milliseconds.
-///
-/// It takes a `Vec<Duration>` of multiple `Duration`s.
+/// Trait for implementing a measurement of an algorithm using a full-text index.
 ///
-/// It returns the average duration in milliseconds as an `f64`.
-pub fn calculate_avg_duration(durations: &[Duration]) -> f64 {
-    if durations.is_empty() {
-        return 0f64;
-    }
-
-    let sum: Duration = durations.iter().sum();
-    let sum_nanos = sum.as_nanos() as f64;
-
-    (sum_nanos / durations.len() as f64) / 1_000_000f64
+/// The index is built only once per text and reused for all patterns and
+/// executions, so only the time it takes to query the index is measured.
+pub trait IndexMeasure {
+    fn measure(
+        &self,
+        pattern: &[u8],
+        index: &FullTextIndex,
+        cli_params: &CLIParams,
+    ) -> SingleMeasurement;
 }
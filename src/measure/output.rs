@@ -0,0 +1,274 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use csv::WriterBuilder;
+use serde::{Deserialize, Serialize};
+
+/// The format the results are written in.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Comma-separated values with one flat row per record.
+    Csv,
+    /// A single JSON array containing one nested object per record.
+    Json,
+    /// One nested JSON object per line and record.
+    Jsonl,
+    /// A human-readable table ranking the algorithms for each pattern
+    /// length, written once all results are known.
+    Table,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::Jsonl),
+            "table" => Ok(Self::Table),
+            _ => Err(format!("unknown output format `{}`", s)),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Infers the format of an output file from its extension, `csv`,
+    /// `json`, `jsonl` (or `ndjson`) or `txt` for a table.
+    ///
+    /// It returns `None` if the extension is missing or unknown.
+    pub fn from_extension(file_name: &str) -> Option<Self> {
+        let extension = Path::new(file_name).extension()?.to_str()?;
+
+        match extension.to_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "jsonl" | "ndjson" => Some(Self::Jsonl),
+            "txt" => Some(Self::Table),
+            _ => None,
+        }
+    }
+}
+
+/// A writer to a file or to stdout which can be shared between threads.
+pub type Sink = Box<dyn Write + Send>;
+
+/// Opens a record writer for each of the given files, `-` standing for
+/// stdout.
+///
+/// The format of each file is inferred from its extension, files with an
+/// unknown extension are written in the `default_format`. The `header` is
+/// only written to CSV files.
+///
+/// It returns an error if a file can't be created.
+pub fn open_writers(
+    file_names: &[String],
+    default_format: OutputFormat,
+    header: bool,
+) -> Result<Vec<RecordWriter<Sink>>, String> {
+    file_names
+        .iter()
+        .map(|file_name| {
+            let sink: Sink = if file_name == "-" {
+                Box::new(io::stdout())
+            } else {
+                let file =
+                    File::create(file_name).map_err(|error| format!("{}: {}", file_name, error))?;
+
+                Box::new(BufWriter::new(file))
+            };
+            let format = OutputFormat::from_extension(file_name).unwrap_or(default_format);
+
+            Ok(RecordWriter::new(sink, format, header))
+        })
+        .collect()
+}
+
+/// Opens a record writer appending to the results file with the given name
+/// in the given `format`, so that an aborted measurement can be continued.
+///
+/// Everything after the first `length` bytes of the file, i. e. the results
+/// of cells that are measured again, is discarded. The `header` is only
+/// written if the file is empty then.
+///
+/// It returns an error if the file can't be opened.
+pub fn append_writer(
+    file_name: &str,
+    format: OutputFormat,
+    length: u64,
+    header: bool,
+) -> Result<RecordWriter<Sink>, String> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_name)
+        .and_then(|file| file.set_len(length).map(|_| file))
+        .map_err(|error| format!("{}: {}", file_name, error))?;
+
+    Ok(RecordWriter::new(
+        Box::new(BufWriter::new(file)),
+        format,
+        header && length == 0,
+    ))
+}
+
+/// A writer of records in one of the output formats.
+///
+/// Each record is flushed to the underlying writer right after it has been
+/// written, so that the results written so far are available even if the
+/// measurement is aborted.
+pub struct RecordWriter<W: Write> {
+    writer: W,
+    format: OutputFormat,
+    /// Whether a header row is written before the first CSV record.
+    header: bool,
+    records: usize,
+}
+
+impl<W: Write> RecordWriter<W> {
+    pub fn new(writer: W, format: OutputFormat, header: bool) -> Self {
+        Self {
+            writer,
+            format,
+            header,
+            records: 0,
+        }
+    }
+
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    /// Writes a single record.
+    ///
+    /// Tables can't be written record by record, as the algorithms are
+    /// ranked, so records are skipped and the table has to be written using
+    /// `write_table` instead.
+    pub fn write<R: Serialize>(&mut self, record: &R) -> Result<(), String> {
+        match self.format {
+            OutputFormat::Table => return Ok(()),
+            OutputFormat::Csv => {
+                let mut wtr = WriterBuilder::new()
+                    .has_headers(self.header && self.records == 0)
+                    .from_writer(&mut self.writer);
+
+                wtr.serialize(record).map_err(|error| error.to_string())?;
+                wtr.flush().map_err(|error| error.to_string())?;
+            }
+            OutputFormat::Json => {
+                let separator: &[u8] = if self.records == 0 { b"[\n" } else { b",\n" };
+
+                self.writer
+                    .write_all(separator)
+                    .map_err(|error| error.to_string())?;
+                serde_json::to_writer(&mut self.writer, record)
+                    .map_err(|error| error.to_string())?;
+            }
+            OutputFormat::Jsonl => {
+                serde_json::to_writer(&mut self.writer, record)
+                    .map_err(|error| error.to_string())?;
+                self.writer
+                    .write_all(b"\n")
+                    .map_err(|error| error.to_string())?;
+            }
+        }
+
+        self.records += 1;
+
+        self.writer.flush().map_err(|error| error.to_string())
+    }
+
+    /// Writes a formatted table if this is the format of the writer.
+    pub fn write_table(&mut self, table: &str) -> Result<(), String> {
+        if self.format != OutputFormat::Table {
+            return Ok(());
+        }
+
+        self.writer
+            .write_all(table.as_bytes())
+            .map_err(|error| error.to_string())
+    }
+
+    /// Completes the output after the last record, i. e. closes the array of
+    /// the JSON format.
+    pub fn finish(&mut self) -> Result<(), String> {
+        if self.format == OutputFormat::Json {
+            let end: &[u8] = if self.records == 0 { b"[]\n" } else { b"\n]\n" };
+
+            self.writer
+                .write_all(end)
+                .map_err(|error| error.to_string())?;
+        }
+
+        self.writer.flush().map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Record {
+        a: usize,
+        b: &'static str,
+    }
+
+    fn written(format: OutputFormat, header: bool, records: &[Record]) -> String {
+        let mut output = Vec::new();
+        let mut writer = RecordWriter::new(&mut output, format, header);
+
+        for record in records {
+            writer.write(record).unwrap();
+        }
+
+        writer.finish().unwrap();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_from_extension() {
+        assert_eq!(
+            OutputFormat::from_extension("results.csv"),
+            Some(OutputFormat::Csv)
+        );
+        assert_eq!(
+            OutputFormat::from_extension("out/summary.JSON"),
+            Some(OutputFormat::Json)
+        );
+        assert_eq!(
+            OutputFormat::from_extension("results.ndjson"),
+            Some(OutputFormat::Jsonl)
+        );
+        assert_eq!(
+            OutputFormat::from_extension("report.txt"),
+            Some(OutputFormat::Table)
+        );
+        assert_eq!(OutputFormat::from_extension("results.tsv"), None);
+        assert_eq!(OutputFormat::from_extension("-"), None);
+    }
+
+    #[test]
+    fn test_record_writer() {
+        let records = [Record { a: 1, b: "x" }, Record { a: 2, b: "y" }];
+
+        assert_eq!(
+            written(OutputFormat::Csv, true, &records),
+            "a,b\n1,x\n2,y\n"
+        );
+        assert_eq!(written(OutputFormat::Csv, false, &records), "1,x\n2,y\n");
+        assert_eq!(
+            written(OutputFormat::Json, true, &records),
+            "[\n{\"a\":1,\"b\":\"x\"},\n{\"a\":2,\"b\":\"y\"}\n]\n"
+        );
+        assert_eq!(written(OutputFormat::Json, true, &[]), "[]\n");
+        assert_eq!(
+            written(OutputFormat::Jsonl, true, &records),
+            "{\"a\":1,\"b\":\"x\"}\n{\"a\":2,\"b\":\"y\"}\n"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The progress of a measurement, reported on stderr so that the CSV output
+/// on stdout stays clean.
+///
+/// The estimated time remaining is extrapolated from the average time the
+/// cells completed so far have taken. If reporting is disabled, nothing is
+/// printed.
+pub struct Progress {
+    enabled: bool,
+    total: usize,
+    completed: AtomicUsize,
+    start: Instant,
+}
+
+impl Progress {
+    /// Starts tracking the progress of measuring `total` cells.
+    pub fn new(total: usize, enabled: bool) -> Self {
+        Self {
+            enabled,
+            total,
+            completed: AtomicUsize::new(0),
+            start: Instant::now(),
+        }
+    }
+
+    /// Reports an `action` on the cell given by its `description`, e. g.
+    /// that it is being measured, together with the number of cells
+    /// completed so far and the estimated time remaining.
+    pub fn report(&self, action: &str, description: &str) {
+        if !self.enabled {
+            return;
+        }
+
+        let completed = self.completed.load(Ordering::SeqCst);
+        let eta = estimate_remaining(self.start.elapsed(), completed, self.total)
+            .map_or(String::from("unknown"), format_duration);
+
+        eprintln!(
+            "[{}/{}] {} {}, ETA {}",
+            completed, self.total, action, description, eta
+        );
+    }
+
+    /// Counts a cell as completed.
+    pub fn complete_cell(&self) {
+        self.completed.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Reports the total time taken to measure all cells.
+    pub fn finish(&self) {
+        if self.enabled {
+            eprintln!(
+                "[{}/{}] Finished in {}",
+                self.completed.load(Ordering::SeqCst),
+                self.total,
+                format_duration(self.start.elapsed())
+            );
+        }
+    }
+}
+
+/// Extrapolates the time remaining to complete `total` cells from the
+/// `elapsed` time it took to complete `completed` cells.
+///
+/// It returns `None` if no cell has been completed yet.
+fn estimate_remaining(elapsed: Duration, completed: usize, total: usize) -> Option<Duration> {
+    if completed == 0 {
+        return None;
+    }
+
+    Some(elapsed.mul_f64(total.saturating_sub(completed) as f64 / completed as f64))
+}
+
+/// Formats a duration in hours, minutes and seconds, omitting leading zero
+/// units.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    if seconds >= 3600 {
+        format!(
+            "{}h {:02}m {:02}s",
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        )
+    } else if seconds >= 60 {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_remaining() {
+        assert_eq!(estimate_remaining(Duration::from_secs(10), 0, 4), None);
+        assert_eq!(
+            estimate_remaining(Duration::from_secs(10), 1, 4),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            estimate_remaining(Duration::from_secs(10), 4, 4),
+            Some(Duration::from_secs(0))
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(7500)), "7s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m 05s");
+        assert_eq!(format_duration(Duration::from_secs(3723)), "1h 02m 03s");
+    }
+}
@@ -0,0 +1,209 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::measure::measurement_result::Status;
+
+/// The median runtimes of an algorithm with one pattern, as shown in a row
+/// of the report.
+pub struct ReportEntry<'a> {
+    /// The name of the algorithm as given by the `--algorithms` argument.
+    pub algorithm: &'a str,
+    pub algorithm_name: &'a str,
+    pub text_length: usize,
+    pub pattern_length: usize,
+    pub status: Status,
+    /// The median preparation time in nanoseconds, `None` for algorithms
+    /// without a preparation phase.
+    pub preparation_median: Option<f64>,
+    /// The median search time in nanoseconds, `None` if the algorithm
+    /// couldn't be measured.
+    pub median: Option<f64>,
+    /// The megabytes of text processed per second at the median search time.
+    pub mb_per_s: Option<f64>,
+}
+
+/// Formats the given entries as a human-readable table for the terminal.
+///
+/// The entries are grouped by text and pattern length. Within each group,
+/// the algorithms are ranked by their median search time, algorithms that
+/// couldn't be measured coming last. If a `baseline` algorithm is given, the
+/// speedup of each algorithm is the median search time of the baseline
+/// divided by its own median search time for the same text and pattern
+/// length.
+pub fn format_report(entries: &[ReportEntry], baseline: Option<&str>) -> String {
+    let mut groups: BTreeMap<(usize, usize), Vec<&ReportEntry>> = BTreeMap::new();
+
+    for entry in entries {
+        groups
+            .entry((entry.text_length, entry.pattern_length))
+            .or_default()
+            .push(entry);
+    }
+
+    let name_width = entries
+        .iter()
+        .map(|x| x.algorithm_name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Algorithm".len());
+
+    let mut report = String::new();
+
+    for ((text_length, pattern_length), mut group) in groups {
+        // Sorting is stable, so algorithms with the same median or without
+        // one keep the order they have been measured in
+        group.sort_by(|a, b| match (a.median, b.median) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap(),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+
+        let baseline_median = baseline.and_then(|baseline| {
+            group
+                .iter()
+                .find(|x| x.algorithm == baseline)
+                .and_then(|x| x.median)
+        });
+
+        if !report.is_empty() {
+            report.push('\n');
+        }
+
+        // Writing to a string can't fail, so unwrapping is safe here
+        writeln!(
+            report,
+            "Text length {}, pattern length {}",
+            text_length, pattern_length
+        )
+        .unwrap();
+        writeln!(
+            report,
+            "{:>4}  {:<name_width$}  {:>12}  {:>12}  {:>14}  {:>8}  Status",
+            "Rank",
+            "Algorithm",
+            "Prep median",
+            "Median",
+            "Throughput",
+            "Speedup",
+            name_width = name_width
+        )
+        .unwrap();
+
+        for (rank, entry) in group.iter().enumerate() {
+            let speedup = match (baseline_median, entry.median) {
+                (Some(baseline_median), Some(median)) if median > 0f64 => {
+                    format!("{:.2}x", baseline_median / median)
+                }
+                _ => String::from("-"),
+            };
+
+            writeln!(
+                report,
+                "{:>4}  {:<name_width$}  {:>12}  {:>12}  {:>14}  {:>8}  {}",
+                entry
+                    .median
+                    .map_or(String::from("-"), |_| (rank + 1).to_string()),
+                entry.algorithm_name,
+                entry
+                    .preparation_median
+                    .map_or(String::from("-"), format_nanos),
+                entry.median.map_or(String::from("-"), format_nanos),
+                entry
+                    .mb_per_s
+                    .map_or(String::from("-"), |x| format!("{:.1} MB/s", x)),
+                speedup,
+                status_name(entry.status),
+                name_width = name_width
+            )
+            .unwrap();
+        }
+    }
+
+    report
+}
+
+/// Formats a duration given in nanoseconds with the largest unit it is at
+/// least one of.
+fn format_nanos(nanos: f64) -> String {
+    if nanos >= 1e9 {
+        format!("{:.2} s", nanos / 1e9)
+    } else if nanos >= 1e6 {
+        format!("{:.2} ms", nanos / 1e6)
+    } else if nanos >= 1e3 {
+        format!("{:.2} µs", nanos / 1e3)
+    } else {
+        format!("{:.0} ns", nanos)
+    }
+}
+
+/// Returns the name of a status as given in the `status` column.
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Ok => "ok",
+        Status::Panic => "panic",
+        Status::Killed => "killed",
+        Status::Crash => "crash",
+        Status::Timeout => "timeout",
+        Status::Skipped => "skipped",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry<'a>(
+        algorithm: &'a str,
+        pattern_length: usize,
+        median: Option<f64>,
+    ) -> ReportEntry<'a> {
+        ReportEntry {
+            algorithm,
+            algorithm_name: algorithm,
+            text_length: 1000,
+            pattern_length,
+            status: if median.is_some() {
+                Status::Ok
+            } else {
+                Status::Timeout
+            },
+            preparation_median: None,
+            median,
+            mb_per_s: median.map(|x| 1e6 / x),
+        }
+    }
+
+    #[test]
+    fn test_format_report() {
+        let entries = vec![
+            entry("naive", 4, Some(4000f64)),
+            entry("kmp", 4, None),
+            entry("horspool", 4, Some(1000f64)),
+            entry("naive", 2, Some(2000f64)),
+        ];
+
+        let report = format_report(&entries, Some("naive"));
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines[0], "Text length 1000, pattern length 2");
+        assert!(lines[2].starts_with("   1  naive"));
+        assert!(lines[2].contains("1.00x"));
+
+        assert_eq!(lines[4], "Text length 1000, pattern length 4");
+        assert!(lines[6].starts_with("   1  horspool"));
+        assert!(lines[6].contains("4.00x"));
+        assert!(lines[7].starts_with("   2  naive"));
+        assert!(lines[8].starts_with("   -  kmp"));
+        assert!(lines[8].ends_with("timeout"));
+    }
+
+    #[test]
+    fn test_format_nanos() {
+        assert_eq!(format_nanos(512f64), "512 ns");
+        assert_eq!(format_nanos(1234f64), "1.23 µs");
+        assert_eq!(format_nanos(2.5e7), "25.00 ms");
+        assert_eq!(format_nanos(3e9), "3.00 s");
+    }
+}
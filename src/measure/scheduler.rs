@@ -0,0 +1,157 @@
+use std::io;
+use std::mem;
+
+use crate::cli::CLIParams;
+
+/// The scheduler settings of the calling thread, applied for the lifetime
+/// of this object.
+///
+/// The measuring thread can be pinned to a single CPU so that it isn't
+/// migrated between cores during the measurement, and its nice value can be
+/// changed to raise its priority over other processes. The previous settings
+/// are restored as soon as this object is dropped.
+pub struct SchedulerSettings {
+    previous_affinity: Option<libc::cpu_set_t>,
+    previous_nice: Option<i32>,
+}
+
+impl SchedulerSettings {
+    /// Applies the CPU pinning and the nice value given in the CLI
+    /// parameters to the calling thread.
+    ///
+    /// Child processes spawned by the thread inherit these settings.
+    ///
+    /// It returns an error if the settings can't be applied, e. g. because
+    /// the CPU doesn't exist or the user isn't allowed to raise the priority.
+    pub fn apply(cli_params: &CLIParams) -> io::Result<Self> {
+        let mut settings = Self {
+            previous_affinity: None,
+            previous_nice: None,
+        };
+
+        if let Some(cpu) = cli_params.pinned_cpu {
+            settings.previous_affinity = Some(affinity()?);
+
+            let mut cpu_set = empty_cpu_set();
+            unsafe { libc::CPU_SET(cpu, &mut cpu_set) };
+
+            set_affinity(&cpu_set).map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("Could not pin the measuring thread to CPU {}: {}", cpu, err),
+                )
+            })?;
+        }
+
+        if let Some(nice) = cli_params.nice {
+            settings.previous_nice = Some(self::nice()?);
+
+            set_nice(nice).map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("Could not set the nice value to {}: {}", nice, err),
+                )
+            })?;
+        }
+
+        Ok(settings)
+    }
+}
+
+impl Drop for SchedulerSettings {
+    fn drop(&mut self) {
+        // Restoring can only fail if the previous settings are invalid now,
+        // in which case there is nothing left to restore
+        if let Some(previous_affinity) = self.previous_affinity {
+            let _ = set_affinity(&previous_affinity);
+        }
+
+        if let Some(previous_nice) = self.previous_nice {
+            let _ = set_nice(previous_nice);
+        }
+    }
+}
+
+/// Returns the maximum number of CPUs a thread can be pinned to.
+pub fn max_cpus() -> usize {
+    libc::CPU_SETSIZE as usize
+}
+
+/// Returns a set of CPUs not containing any CPU.
+fn empty_cpu_set() -> libc::cpu_set_t {
+    // A zeroed cpu_set_t is a valid, empty set
+    unsafe { mem::zeroed() }
+}
+
+/// Returns the set of CPUs the calling thread is allowed to run on.
+fn affinity() -> io::Result<libc::cpu_set_t> {
+    let mut cpu_set = empty_cpu_set();
+
+    let result =
+        unsafe { libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut cpu_set) };
+
+    if result == 0 {
+        Ok(cpu_set)
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Restricts the calling thread to the given set of CPUs.
+fn set_affinity(cpu_set: &libc::cpu_set_t) -> io::Result<()> {
+    let result = unsafe { libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), cpu_set) };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Returns the nice value of the calling thread.
+fn nice() -> io::Result<i32> {
+    // -1 is a valid nice value, so errors can only be told apart by errno
+    unsafe { *libc::__errno_location() = 0 };
+
+    let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS, 0) };
+
+    if nice == -1 && io::Error::last_os_error().raw_os_error() != Some(0) {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(nice)
+    }
+}
+
+/// Sets the nice value of the calling thread, lower values meaning a higher
+/// priority.
+fn set_nice(nice: i32) -> io::Result<()> {
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_affinity() {
+        let cpu_set = affinity().unwrap();
+
+        // Setting the current affinity again never fails
+        set_affinity(&cpu_set).unwrap();
+
+        assert!((0..max_cpus()).any(|cpu| unsafe { libc::CPU_ISSET(cpu, &cpu_set) }));
+    }
+
+    #[test]
+    fn test_nice() {
+        let nice = nice().unwrap();
+
+        assert!((-20..=19).contains(&nice));
+    }
+}
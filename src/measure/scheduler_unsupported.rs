@@ -0,0 +1,28 @@
+use std::io;
+
+use crate::cli::CLIParams;
+
+/// The scheduler settings of the calling thread, which can only be changed
+/// on Linux.
+pub struct SchedulerSettings;
+
+impl SchedulerSettings {
+    /// Returns an error if CPU pinning or a nice value is given in the CLI
+    /// parameters, as neither can be applied on this platform.
+    pub fn apply(cli_params: &CLIParams) -> io::Result<Self> {
+        if cli_params.pinned_cpu.is_some() || cli_params.nice.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "CPU pinning and nice values are only supported on Linux",
+            ));
+        }
+
+        Ok(Self)
+    }
+}
+
+/// Returns the maximum number of CPUs a thread can be pinned to, which is
+/// none on this platform.
+pub fn max_cpus() -> usize {
+    0
+}
@@ -0,0 +1,315 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Critical values of Student's t-distribution for a two-sided 95%
+/// confidence interval, indexed by the degrees of freedom minus one.
+const T_CRITICAL_VALUES_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Critical value of the standard normal distribution for a two-sided 95%
+/// confidence interval, used for more than 30 degrees of freedom.
+const Z_CRITICAL_VALUE_95: f64 = 1.960;
+
+/// The factor of the interquartile range by which a sample has to lie outside
+/// of the quartiles to be classified as an outlier by Tukey's fences.
+const TUKEY_FENCE_FACTOR: f64 = 1.5;
+
+/// The modified z-score above which a sample is classified as an outlier
+/// using the median absolute deviation.
+const MODIFIED_Z_SCORE_THRESHOLD: f64 = 3.5;
+
+/// A method to classify samples as outliers.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum OutlierDetection {
+    /// No sample is classified as an outlier.
+    Disabled,
+    /// Samples outside of Tukey's fences are classified as outliers.
+    Tukey,
+    /// Samples whose modified z-score, which is based on the median absolute
+    /// deviation, is too large are classified as outliers.
+    MedianAbsoluteDeviation,
+}
+
+impl FromStr for OutlierDetection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::Disabled),
+            "tukey" => Ok(Self::Tukey),
+            "mad" => Ok(Self::MedianAbsoluteDeviation),
+            _ => Err(format!("unknown outlier detection method `{}`", s)),
+        }
+    }
+}
+
+impl OutlierDetection {
+    /// Classifies each of the given samples as an outlier or not.
+    ///
+    /// It returns a `Vec<bool>` that is `true` at the indices of the outliers.
+    pub fn classify(&self, samples: &[f64]) -> Vec<bool> {
+        if samples.is_empty() {
+            return Vec::new();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        match self {
+            Self::Disabled => vec![false; samples.len()],
+            Self::Tukey => {
+                let q1 = percentile(&sorted, 25f64);
+                let q3 = percentile(&sorted, 75f64);
+                let iqr = q3 - q1;
+
+                let lower_fence = q1 - TUKEY_FENCE_FACTOR * iqr;
+                let upper_fence = q3 + TUKEY_FENCE_FACTOR * iqr;
+
+                samples
+                    .iter()
+                    .map(|x| *x < lower_fence || *x > upper_fence)
+                    .collect()
+            }
+            Self::MedianAbsoluteDeviation => {
+                let median = percentile(&sorted, 50f64);
+
+                let mut deviations: Vec<f64> = samples.iter().map(|x| (x - median).abs()).collect();
+                deviations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let mad = percentile(&deviations, 50f64);
+
+                // If more than half of the samples are equal to the median,
+                // the deviation of the others can't be rated
+                if mad == 0f64 {
+                    return vec![false; samples.len()];
+                }
+
+                // The constant scales the modified z-score so that it is
+                // comparable to the standard z-score for normal distributions
+                samples
+                    .iter()
+                    .map(|x| 0.6745 * (x - median).abs() / mad > MODIFIED_Z_SCORE_THRESHOLD)
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Summary statistics of the runtimes of multiple executions of one phase
+/// of an algorithm, all values given in nanoseconds.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Summary {
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub standard_deviation: f64,
+    pub p5: f64,
+    pub p95: f64,
+    /// The 95% confidence interval of the mean, `None` if there are less
+    /// than two samples.
+    pub confidence_interval: Option<(f64, f64)>,
+}
+
+impl Summary {
+    /// Calculates the summary statistics of the given samples.
+    ///
+    /// It returns `None` if there are no samples.
+    pub fn new(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mean = mean(samples);
+
+        Some(Self {
+            mean,
+            median: percentile(&sorted, 50f64),
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            standard_deviation: standard_deviation(samples),
+            p5: percentile(&sorted, 5f64),
+            p95: percentile(&sorted, 95f64),
+            confidence_interval: confidence_interval_half_width(samples)
+                .map(|half_width| (mean - half_width, mean + half_width)),
+        })
+    }
+}
+
+/// Converts durations to nanoseconds as `f64` values.
+pub fn durations_as_nanos(durations: &[Duration]) -> Vec<f64> {
+    durations.iter().map(|x| x.as_nanos() as f64).collect()
+}
+
+/// Calculates the arithmetic mean of the given samples.
+///
+/// It returns `0` if there are no samples.
+pub fn mean(samples: &[f64]) -> f64 {
+    if samples.is_empty() {
+        return 0f64;
+    }
+
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+/// Calculates the `p`-th percentile of the given sorted samples, linearly
+/// interpolating between the two closest ranks.
+///
+/// It returns `0` if there are no samples.
+pub fn percentile(sorted_samples: &[f64], p: f64) -> f64 {
+    if sorted_samples.is_empty() {
+        return 0f64;
+    }
+
+    let rank = p / 100f64 * (sorted_samples.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+
+    sorted_samples[lower] + (sorted_samples[upper] - sorted_samples[lower]) * (rank - lower as f64)
+}
+
+/// Calculates the sample standard deviation of the given samples.
+///
+/// It returns `0` if there are less than two samples.
+pub fn standard_deviation(samples: &[f64]) -> f64 {
+    if samples.len() < 2 {
+        return 0f64;
+    }
+
+    let mean = mean(samples);
+    let sum_of_squares: f64 = samples.iter().map(|x| (x - mean) * (x - mean)).sum();
+
+    (sum_of_squares / (samples.len() - 1) as f64).sqrt()
+}
+
+/// Calculates the half width of the 95% confidence interval of the mean
+/// of the given samples using Student's t-distribution.
+///
+/// It returns `None` if there are less than two samples.
+pub fn confidence_interval_half_width(samples: &[f64]) -> Option<f64> {
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let degrees_of_freedom = samples.len() - 1;
+    let t = T_CRITICAL_VALUES_95
+        .get(degrees_of_freedom - 1)
+        .copied()
+        .unwrap_or(Z_CRITICAL_VALUE_95);
+
+    Some(t * standard_deviation(samples) / (samples.len() as f64).sqrt())
+}
+
+/// Calculates the width of the 95% confidence interval of the mean relative
+/// to the mean itself.
+///
+/// It returns `None` if there are less than two samples or the mean is `0`.
+pub fn relative_confidence_interval_width(samples: &[f64]) -> Option<f64> {
+    let mean = mean(samples);
+
+    if mean == 0f64 {
+        return None;
+    }
+
+    confidence_interval_half_width(samples).map(|half_width| 2f64 * half_width / mean)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mean() {
+        assert_eq!(mean(&[]), 0f64);
+        assert_eq!(mean(&[1f64, 2f64, 3f64, 6f64]), 3f64);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples = [1f64, 2f64, 3f64, 4f64, 5f64];
+
+        assert_eq!(percentile(&samples, 0f64), 1f64);
+        assert_eq!(percentile(&samples, 50f64), 3f64);
+        assert_eq!(percentile(&samples, 100f64), 5f64);
+        assert_eq!(percentile(&samples, 5f64), 1.2);
+        assert_eq!(percentile(&[1f64, 2f64], 50f64), 1.5);
+    }
+
+    #[test]
+    fn test_outlier_detection_tukey() {
+        let samples = [10f64, 11f64, 12f64, 11f64, 10f64, 100f64];
+
+        assert_eq!(
+            OutlierDetection::Tukey.classify(&samples),
+            vec![false, false, false, false, false, true]
+        );
+        assert_eq!(
+            OutlierDetection::Disabled.classify(&samples),
+            vec![false; samples.len()]
+        );
+    }
+
+    #[test]
+    fn test_outlier_detection_mad() {
+        let samples = [10f64, 11f64, 12f64, 11f64, 10f64, 100f64];
+
+        assert_eq!(
+            OutlierDetection::MedianAbsoluteDeviation.classify(&samples),
+            vec![false, false, false, false, false, true]
+        );
+        assert_eq!(
+            OutlierDetection::MedianAbsoluteDeviation.classify(&[5f64, 5f64, 5f64, 6f64]),
+            vec![false; 4]
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = Summary::new(&[14f64, 10f64, 12f64]).unwrap();
+
+        assert_eq!(summary.mean, 12f64);
+        assert_eq!(summary.median, 12f64);
+        assert_eq!(summary.min, 10f64);
+        assert_eq!(summary.max, 14f64);
+        assert_eq!(summary.standard_deviation, 2f64);
+        assert!(summary.confidence_interval.is_some());
+
+        assert_eq!(Summary::new(&[]), None);
+        assert_eq!(Summary::new(&[1f64]).unwrap().confidence_interval, None);
+    }
+
+    #[test]
+    fn test_standard_deviation() {
+        let samples = [2f64, 4f64, 4f64, 4f64, 5f64, 5f64, 7f64, 9f64];
+
+        assert_eq!(standard_deviation(&[1f64]), 0f64);
+        assert!((standard_deviation(&samples) - 2.138_089_935).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_confidence_interval_half_width() {
+        let samples = [10f64, 12f64, 14f64];
+
+        assert_eq!(confidence_interval_half_width(&[10f64]), None);
+        // Standard deviation is 2, t-value for two degrees of freedom is 4.303
+        assert!(
+            (confidence_interval_half_width(&samples).unwrap() - 4.303 * 2f64 / 3f64.sqrt()).abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_relative_confidence_interval_width() {
+        let samples = [10f64, 10f64, 10f64];
+
+        assert_eq!(relative_confidence_interval_width(&samples), Some(0f64));
+        assert_eq!(relative_confidence_interval_width(&[0f64, 0f64]), None);
+    }
+}
@@ -0,0 +1,93 @@
+use serde::Serialize;
+
+/// Metrics derived from the runtime of a single phase of an algorithm which
+/// normalize the runtime by the size of its input and output, so that
+/// measurements using different texts and patterns can be compared.
+///
+/// Each metric is `None` if it is undefined, e. g. because the runtime, the
+/// text or the pattern is empty or there haven't been any matches.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Throughput {
+    /// Megabytes (10^6 bytes) of text processed per second.
+    pub mb_per_s: Option<f64>,
+    /// Nanoseconds per byte of text.
+    pub ns_per_byte: Option<f64>,
+    /// Nanoseconds per character of the pattern, only set for index queries.
+    pub ns_per_pattern_char: Option<f64>,
+    /// Nanoseconds per reported occurrence, only set for index queries.
+    pub ns_per_occurrence: Option<f64>,
+}
+
+impl Throughput {
+    /// Calculates the normalized metrics of a runtime of `time_ns`
+    /// nanoseconds.
+    ///
+    /// It takes the lengths of the text and the pattern, the number of
+    /// matches and whether the runtime is the one of a query of a full-text
+    /// index. Index queries don't scan the text, so their runtime is also
+    /// normalized by the pattern length and the number of occurrences.
+    pub fn new(
+        time_ns: f64,
+        text_length: usize,
+        pattern_length: usize,
+        matches: usize,
+        index_query: bool,
+    ) -> Self {
+        let per = |count: usize| {
+            if time_ns > 0f64 && count > 0 {
+                Some(time_ns / count as f64)
+            } else {
+                None
+            }
+        };
+
+        // Bytes per nanosecond times 10^9 / 10^6
+        let mb_per_s = per(text_length).map(|ns_per_byte| 1e3 / ns_per_byte);
+
+        if index_query {
+            Self {
+                mb_per_s,
+                ns_per_byte: per(text_length),
+                ns_per_pattern_char: per(pattern_length),
+                ns_per_occurrence: per(matches),
+            }
+        } else {
+            Self {
+                mb_per_s,
+                ns_per_byte: per(text_length),
+                ..Self::default()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throughput() {
+        let throughput = Throughput::new(2_000_000f64, 1_000_000, 4, 10, false);
+
+        assert_eq!(throughput.mb_per_s, Some(500f64));
+        assert_eq!(throughput.ns_per_byte, Some(2f64));
+        assert_eq!(throughput.ns_per_pattern_char, None);
+        assert_eq!(throughput.ns_per_occurrence, None);
+
+        let throughput = Throughput::new(400f64, 1_000_000, 4, 10, true);
+
+        assert_eq!(throughput.ns_per_pattern_char, Some(100f64));
+        assert_eq!(throughput.ns_per_occurrence, Some(40f64));
+        assert_eq!(
+            Throughput::new(400f64, 1_000, 4, 0, true).ns_per_occurrence,
+            None
+        );
+    }
+
+    #[test]
+    fn test_throughput_zero_time() {
+        let throughput = Throughput::new(0f64, 1_000, 4, 10, false);
+
+        assert_eq!(throughput, Throughput::default());
+    }
+}
@@ -0,0 +1,130 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The minimum duration a timed run has to take to be measured reliably.
+///
+/// Runs that are shorter than this are repeated in a batch that takes
+/// at least this long.
+const MIN_BATCH_DURATION: Duration = Duration::from_millis(1);
+
+/// The maximum number of iterations of a single batch.
+const MAX_BATCH_ITERATIONS: u32 = 1_000_000;
+
+/// The number of samples taken to calibrate the timer overhead.
+const CALIBRATION_SAMPLES: usize = 10_000;
+
+lazy_static! {
+    /// The overhead of taking two consecutive timestamps, calibrated once
+    /// on first use.
+    static ref TIMER_OVERHEAD: Duration = calibrate_overhead();
+}
+
+/// Measures the runtime of a function using a monotonic clock.
+///
+/// If a single execution of `f` is too short to be measured reliably, `f`
+/// is executed repeatedly in an automatically sized batch and the average
+/// runtime of a single iteration of this batch is returned. The calibrated
+/// timer overhead is subtracted from the measured duration.
+///
+/// It returns the result of the last execution of `f` and the runtime
+/// of a single execution.
+pub fn measure<R, F: FnMut() -> R>(mut f: F) -> (R, Duration) {
+    let before = Instant::now();
+    let mut result = black_box(f());
+    let elapsed = before.elapsed();
+
+    if elapsed >= MIN_BATCH_DURATION {
+        return (result, subtract_overhead(elapsed));
+    }
+
+    let iterations = batch_iterations(elapsed);
+
+    let before = Instant::now();
+
+    for _ in 0..iterations {
+        result = black_box(f());
+    }
+
+    let elapsed = before.elapsed();
+
+    (result, subtract_overhead(elapsed) / iterations)
+}
+
+/// Measures the runtime of a single execution of a function using a
+/// monotonic clock.
+///
+/// Unlike `measure()`, it never repeats `f`, even if its runtime is too short
+/// to be measured reliably, so that the state `f` is executed in, e. g. the
+/// state of the caches, is preserved. The calibrated timer overhead is
+/// subtracted from the measured duration.
+///
+/// It returns the result of `f` and its runtime.
+pub fn measure_once<R, F: FnOnce() -> R>(f: F) -> (R, Duration) {
+    let before = Instant::now();
+    let result = black_box(f());
+    let elapsed = before.elapsed();
+
+    (result, subtract_overhead(elapsed))
+}
+
+/// Calculates how often a run taking `elapsed` has to be repeated so that
+/// the whole batch takes at least `MIN_BATCH_DURATION`.
+fn batch_iterations(elapsed: Duration) -> u32 {
+    // Avoid dividing by zero for runs below the resolution of the clock
+    let elapsed_nanos = elapsed.as_nanos().max(1);
+    let iterations = MIN_BATCH_DURATION.as_nanos() / elapsed_nanos + 1;
+
+    iterations.min(MAX_BATCH_ITERATIONS as u128) as u32
+}
+
+/// Subtracts the calibrated timer overhead from a measured duration.
+fn subtract_overhead(elapsed: Duration) -> Duration {
+    elapsed
+        .checked_sub(*TIMER_OVERHEAD)
+        .unwrap_or_else(|| Duration::new(0, 0))
+}
+
+/// Calibrates the overhead of taking two consecutive timestamps.
+///
+/// It returns the median of multiple samples so that single outliers
+/// caused by interrupts or the scheduler are ignored.
+fn calibrate_overhead() -> Duration {
+    let mut samples: Vec<Duration> = (0..CALIBRATION_SAMPLES)
+        .map(|_| {
+            let before = Instant::now();
+
+            before.elapsed()
+        })
+        .collect();
+
+    samples.sort_unstable();
+
+    samples[samples.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_iterations() {
+        assert_eq!(batch_iterations(Duration::from_millis(2)), 1);
+        assert_eq!(batch_iterations(Duration::from_micros(100)), 11);
+        assert_eq!(batch_iterations(Duration::new(0, 0)), MAX_BATCH_ITERATIONS);
+    }
+
+    #[test]
+    fn test_measure_returns_result() {
+        let mut executions = 0;
+
+        let (result, _) = measure(|| {
+            executions += 1;
+
+            executions
+        });
+
+        // The last execution's result is returned, so it has to match
+        // the number of executions
+        assert_eq!(result, executions);
+    }
+}
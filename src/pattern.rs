@@ -2,11 +2,13 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufReader;
 
+use serde::Serialize;
+
 use crate::cli::CLIParams;
 use crate::generate::{gen_rand_bytes, rand_pattern_from_bytes};
 use crate::range::Range;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum PatternSource {
     FromArgument(Vec<String>),
     FromFile(String, bool),
@@ -50,7 +52,8 @@ pub fn generate_patterns(cli_params: &'_ CLIParams, text: &[u8]) -> Result<Vec<V
             let mut patterns = Vec::new();
 
             for length in range.iter() {
-                patterns.push(rand_pattern_from_bytes(text, length, cli_params.seed).to_vec());
+                patterns
+                    .push(rand_pattern_from_bytes(text, length, Some(cli_params.seed)).to_vec());
             }
 
             Ok(patterns)
@@ -74,7 +77,12 @@ pub fn generate_patterns(cli_params: &'_ CLIParams, text: &[u8]) -> Result<Vec<V
 
             // Generate random patterns
             for length in range.iter() {
-                patterns.push(gen_rand_bytes(length, cli_params.seed, None, Some(&chars)));
+                patterns.push(gen_rand_bytes(
+                    length,
+                    Some(cli_params.seed),
+                    None,
+                    Some(&chars),
+                ));
             }
 
             Ok(patterns)
@@ -2,6 +2,7 @@ use std::fmt;
 use std::str::FromStr;
 
 use regex::Regex;
+use serde::Serialize;
 
 /// A struct to represent a range starting and ending at specific values
 /// and increasing by a given step size.
@@ -10,7 +11,7 @@ use regex::Regex;
 ///
 /// Can be represented as a string `start..end,step_size` with the `,step_size`
 /// part being option and can be parsed from a `Str` using `FromStr`.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Range {
     pub start: usize,
     pub end: usize,
@@ -2,10 +2,12 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufReader;
 
+use serde::Serialize;
+
 use crate::cli::CLIParams;
 use crate::generate::gen_rand_bytes;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum TextSource {
     RandomText(usize),
     FromFile(String),
@@ -19,7 +21,7 @@ pub fn generate_text(cli_params: &CLIParams) -> Result<Vec<u8>, String> {
     match &cli_params.text_source {
         TextSource::RandomText(n) => Ok(gen_rand_bytes(
             *n,
-            cli_params.seed,
+            Some(cli_params.seed),
             Some(cli_params.alphabet_size),
             None,
         )),